@@ -0,0 +1,66 @@
+//! Example solver plugin providing a hash-count day1 part 2.
+//!
+//! Build it with `cargo build --release --example plugin_day1` and copy
+//! `target/release/examples/libplugin_day1.so` into `plugins/`. It then runs as `day1@hashcount`.
+//! See `src/plugin.rs` for the ABI.
+
+use std::{
+    collections::HashMap,
+    ffi::{c_char, c_void},
+    ptr, slice,
+};
+
+const ABI_VERSION: u32 = 3;
+
+type SolveFn = unsafe extern "C" fn(*const u8, usize, *mut u8, usize) -> isize;
+type RegisterFn = unsafe extern "C" fn(*mut c_void, u32, u32, u32, *const c_char, SolveFn);
+
+/// # Safety
+///
+/// Called by the runner with a valid registrar and register callback.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_plugin_init(
+    abi_version: u32,
+    registrar: *mut c_void,
+    register: RegisterFn,
+) -> i32 {
+    if abi_version != ABI_VERSION {
+        return 1;
+    }
+
+    register(registrar, 2024, 1, 2, c"hashcount".as_ptr(), part2);
+    0
+}
+
+unsafe extern "C" fn part2(
+    input: *const u8,
+    input_len: usize,
+    out: *mut u8,
+    out_cap: usize,
+) -> isize {
+    let Ok(input) = std::str::from_utf8(slice::from_raw_parts(input, input_len)) else {
+        return -1;
+    };
+
+    let mut left = vec![];
+    let mut right_counts: HashMap<i64, i64> = HashMap::new();
+    for line in input.lines() {
+        let mut nums = line.split_whitespace().map(|n| n.parse::<i64>());
+        let (Some(Ok(l)), Some(Ok(r))) = (nums.next(), nums.next()) else {
+            return -1;
+        };
+        left.push(l);
+        *right_counts.entry(r).or_default() += 1;
+    }
+
+    let answer: i64 = left
+        .iter()
+        .map(|l| l * right_counts.get(l).unwrap_or(&0))
+        .sum();
+
+    let answer = format!("int\n{answer}");
+    if answer.len() <= out_cap {
+        ptr::copy_nonoverlapping(answer.as_ptr(), out, answer.len());
+    }
+    answer.len() as isize
+}
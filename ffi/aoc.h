@@ -0,0 +1,43 @@
+/*
+ * The C ABI of the Advent of Code solutions. Build the shared library with
+ *
+ *     cargo rustc --release --lib --features ffi --crate-type cdylib
+ *
+ * and link against target/release/libadvent_of_code_2024.so. A plain cargo build makes only the
+ * Rust library, so the shared library is built on request like this.
+ */
+
+#ifndef AOC_H
+#define AOC_H
+
+#include <stddef.h>
+#include <stdint.h>
+#include <sys/types.h>
+
+#define AOC_ERR_UNKNOWN_DAY (-1)
+#define AOC_ERR_UNKNOWN_PART (-2)
+/* The input pointer was null or the input is not UTF-8. */
+#define AOC_ERR_INVALID_INPUT (-3)
+/* The day panicked while parsing the input. */
+#define AOC_ERR_PARSE (-4)
+/* The day panicked while preparing the input or solving the part. */
+#define AOC_ERR_PANIC (-5)
+/* The part has no answer for this input, e.g. because the guard walks in a loop. */
+#define AOC_ERR_NO_ANSWER (-6)
+
+/*
+ * Solves part `part` of day `day` of the latest year from `input_len` bytes of UTF-8 input and
+ * writes the answer, without a terminating NUL, into `out`. Returns the answer's length, which
+ * is more than `out_len` if the buffer was too small and nothing was written, or one of the
+ * AOC_ERR_* codes.
+ */
+ssize_t aoc_solve(uint32_t day, uint32_t part, const uint8_t *input, size_t input_len,
+                  uint8_t *out, size_t out_len);
+
+/*
+ * Writes the message of the last failed aoc_solve on this thread into `out`, returning its
+ * length in the same way.
+ */
+ssize_t aoc_last_error(uint8_t *out, size_t out_len);
+
+#endif
@@ -0,0 +1,52 @@
+/*
+ * Checks the C ABI from C. After building the shared library as described in aoc.h, run
+ *
+ *     cc -Wall -o target/test_aoc ffi/test_aoc.c -Ltarget/release -ladvent_of_code_2024
+ *     LD_LIBRARY_PATH=target/release target/test_aoc
+ */
+
+#include "aoc.h"
+
+#include <stdio.h>
+#include <string.h>
+
+static int failures = 0;
+
+static void check(int ok, const char *what) {
+    printf("%s %s\n", ok ? "ok" : "FAILED", what);
+    if (!ok) {
+        failures++;
+    }
+}
+
+static ssize_t solve(uint32_t day, uint32_t part, const char *input, char *out, size_t out_len) {
+    return aoc_solve(day, part, (const uint8_t *)input, strlen(input), (uint8_t *)out, out_len);
+}
+
+int main(void) {
+    const char *example = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+    char out[64];
+    ssize_t len;
+
+    len = solve(1, 1, example, out, sizeof out);
+    check(len == 2 && memcmp(out, "11", 2) == 0, "day 1 part 1");
+    len = solve(1, 2, example, out, sizeof out);
+    check(len == 2 && memcmp(out, "31", 2) == 0, "day 1 part 2");
+    check(solve(1, 2, example, NULL, 0) == 2, "answer length without a buffer");
+
+    check(solve(26, 1, example, out, sizeof out) == AOC_ERR_UNKNOWN_DAY, "unknown day");
+    len = aoc_last_error((uint8_t *)out, sizeof out);
+    check(len > 0 && (size_t)len < sizeof out, "last error");
+    if (len > 0 && (size_t)len < sizeof out) {
+        printf("   %.*s\n", (int)len, out);
+    }
+    check(solve(1, 3, example, out, sizeof out) == AOC_ERR_UNKNOWN_PART, "unknown part");
+    check(solve(1, 1, "\xff", out, sizeof out) == AOC_ERR_INVALID_INPUT, "input not UTF-8");
+    check(aoc_solve(1, 1, NULL, 4, (uint8_t *)out, sizeof out) == AOC_ERR_INVALID_INPUT,
+          "null input");
+    check(solve(1, 1, "1 x\n", out, sizeof out) == AOC_ERR_PARSE, "unparsable input");
+    check(solve(6, 1, ".#..\n...#\n#^..\n..#.\n", out, sizeof out) == AOC_ERR_NO_ANSWER,
+          "no answer");
+
+    return failures == 0 ? 0 : 1;
+}
@@ -1,8 +1,337 @@
-#[macro_export]
-macro_rules! get_data_filepath {
-    () => {{
-        let filepath = std::path::Path::new(file!());
-        let filestem = filepath.file_stem().unwrap().to_string_lossy();
-        std::path::PathBuf::from(format!("data/{}.txt", filestem))
-    }};
+//! Grid geometry shared between days. Not every day uses every helper.
+
+use std::{
+    fmt,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+/// A position on a grid. `y` grows downwards, so row `y` of the input is `grid[y]`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// The difference between two points.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vec2 {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const fn new(x: i64, y: i64) -> Point {
+        Point { x, y }
+    }
+
+    pub fn in_bounds(self, width: usize, height: usize) -> bool {
+        self.x >= 0 && self.y >= 0 && (self.x as usize) < width && (self.y as usize) < height
+    }
+
+    /// The cell at this point, or `None` if it is off the grid.
+    pub fn get<T>(self, grid: &[Vec<T>]) -> Option<&T> {
+        let row = grid.get(usize::try_from(self.y).ok()?)?;
+        row.get(usize::try_from(self.x).ok()?)
+    }
+
+    /// The points one step away in each of `directions`.
+    pub fn neighbors(self, directions: &[Direction]) -> impl Iterator<Item = Point> + '_ {
+        directions.iter().map(move |dir| self + dir.offset())
+    }
+}
+
+impl Vec2 {
+    pub const fn new(x: i64, y: i64) -> Vec2 {
+        Vec2 { x, y }
+    }
+}
+
+impl Add<Vec2> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Vec2) -> Point {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub<Vec2> for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Vec2) -> Point {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Vec2;
+
+    fn sub(self, rhs: Point) -> Vec2 {
+        Vec2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl AddAssign<Vec2> for Point {
+    fn add_assign(&mut self, rhs: Vec2) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign<Vec2> for Point {
+    fn sub_assign(&mut self, rhs: Vec2) {
+        *self = *self - rhs;
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, rhs: Vec2) -> Vec2 {
+        Vec2::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Vec2;
+
+    fn sub(self, rhs: Vec2) -> Vec2 {
+        Vec2::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Mul<i64> for Vec2 {
+    type Output = Vec2;
+
+    fn mul(self, rhs: i64) -> Vec2 {
+        Vec2::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Vec2;
+
+    fn neg(self) -> Vec2 {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
+/// A compass direction on a grid, where north is up.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    /// The four directions without diagonals, clockwise from north.
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// All eight directions, clockwise from north.
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    fn rotate(self, eighths: usize) -> Direction {
+        Direction::ALL[(self as usize + eighths) % 8]
+    }
+
+    pub fn turn_right(self) -> Direction {
+        self.rotate(2)
+    }
+
+    pub fn turn_left(self) -> Direction {
+        self.rotate(6)
+    }
+
+    pub fn turn_right_45(self) -> Direction {
+        self.rotate(1)
+    }
+
+    pub fn turn_left_45(self) -> Direction {
+        self.rotate(7)
+    }
+
+    pub fn opposite(self) -> Direction {
+        self.rotate(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        !Direction::CARDINAL.contains(&self)
+    }
+
+    pub fn offset(self) -> Vec2 {
+        match self {
+            Direction::North => Vec2::new(0, -1),
+            Direction::NorthEast => Vec2::new(1, -1),
+            Direction::East => Vec2::new(1, 0),
+            Direction::SouthEast => Vec2::new(1, 1),
+            Direction::South => Vec2::new(0, 1),
+            Direction::SouthWest => Vec2::new(-1, 1),
+            Direction::West => Vec2::new(-1, 0),
+            Direction::NorthWest => Vec2::new(-1, -1),
+        }
+    }
+
+    /// A single character for drawing this direction on a grid.
+    pub fn arrow(self) -> char {
+        match self {
+            Direction::North => '^',
+            Direction::East => '>',
+            Direction::South => 'v',
+            Direction::West => '<',
+            Direction::NorthEast | Direction::SouthWest => '/',
+            Direction::SouthEast | Direction::NorthWest => '\\',
+        }
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = String;
+
+    /// Parses arrows (`^>v<`), compass letters (`NESW`) or `UDLR`.
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '^' | 'N' | 'U' => Ok(Direction::North),
+            '>' | 'E' | 'R' => Ok(Direction::East),
+            'v' | 'S' | 'D' => Ok(Direction::South),
+            '<' | 'W' | 'L' => Ok(Direction::West),
+            _ => Err(format!("Invalid direction: {c}")),
+        }
+    }
+}
+
+/// Parses a single direction character, or a diagonal such as `NE`.
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next(), chars.next()) {
+            (Some(c), None, None) => Direction::try_from(c),
+            (Some(_), Some(_), None) => match s {
+                "NE" => Ok(Direction::NorthEast),
+                "SE" => Ok(Direction::SouthEast),
+                "SW" => Ok(Direction::SouthWest),
+                "NW" => Ok(Direction::NorthWest),
+                _ => Err(format!("Invalid direction: {s}")),
+            },
+            _ => Err(format!("Invalid direction: {s}")),
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Direction::North => "N",
+            Direction::NorthEast => "NE",
+            Direction::East => "E",
+            Direction::SouthEast => "SE",
+            Direction::South => "S",
+            Direction::SouthWest => "SW",
+            Direction::West => "W",
+            Direction::NorthWest => "NW",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point_arithmetic() {
+        let a = Point::new(3, 4);
+        let b = Point::new(5, 1);
+        let diff = b - a;
+        assert_eq!(diff, Vec2::new(2, -3));
+        assert_eq!(a + diff, b);
+        assert_eq!(b - diff, a);
+        assert_eq!(a + diff * 2, Point::new(7, -2));
+        assert_eq!(-diff + diff, Vec2::default());
+
+        let mut c = a;
+        c += Vec2::new(1, 1);
+        c -= Vec2::new(0, 2);
+        assert_eq!(c, Point::new(4, 3));
+    }
+
+    #[test]
+    fn test_point_grid() {
+        let grid = vec![vec![1, 2, 3], vec![4, 5, 6]];
+        assert_eq!(Point::new(2, 1).get(&grid), Some(&6));
+        assert_eq!(Point::new(3, 0).get(&grid), None);
+        assert_eq!(Point::new(-1, 0).get(&grid), None);
+        assert!(Point::new(2, 1).in_bounds(3, 2));
+        assert!(!Point::new(0, 2).in_bounds(3, 2));
+
+        let neighbors: Vec<Point> = Point::new(0, 0).neighbors(&Direction::CARDINAL).collect();
+        assert_eq!(
+            neighbors,
+            vec![
+                Point::new(0, -1),
+                Point::new(1, 0),
+                Point::new(0, 1),
+                Point::new(-1, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_direction_turns() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::West.turn_right_45(), Direction::NorthWest);
+        assert_eq!(Direction::North.turn_left_45(), Direction::NorthWest);
+        assert_eq!(Direction::SouthEast.opposite(), Direction::NorthWest);
+        for dir in Direction::ALL {
+            assert_eq!(dir.offset() + dir.opposite().offset(), Vec2::default());
+            assert_eq!(dir.turn_right().turn_left(), dir);
+        }
+        assert!(Direction::SouthWest.is_diagonal());
+        assert!(!Direction::South.is_diagonal());
+    }
+
+    #[test]
+    fn test_direction_parse() {
+        for (s, dir) in [
+            ("^", Direction::North),
+            ("E", Direction::East),
+            ("D", Direction::South),
+            ("<", Direction::West),
+            ("NE", Direction::NorthEast),
+        ] {
+            assert_eq!(s.parse::<Direction>().unwrap(), dir);
+        }
+        assert_eq!(
+            "x".parse::<Direction>().unwrap_err(),
+            "Invalid direction: x"
+        );
+        assert_eq!(
+            "NNE".parse::<Direction>().unwrap_err(),
+            "Invalid direction: NNE"
+        );
+        for dir in Direction::ALL {
+            assert_eq!(dir.to_string().parse::<Direction>().unwrap(), dir);
+        }
+    }
 }
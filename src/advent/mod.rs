@@ -1,11 +1,61 @@
-mod common;
-pub mod day1;
-pub mod day10;
-pub mod day2;
-pub mod day3;
-pub mod day4;
-pub mod day5;
-pub mod day6;
-pub mod day7;
-pub mod day8;
-pub mod day9;
+pub mod common;
+pub mod y2024;
+
+use crate::{
+    input::InputSource,
+    lint::Violation,
+    minimize::Shape,
+    rng::Rng,
+    solution::{DayRun, RunVariants},
+    trace::Trace,
+    viz::Frames,
+};
+
+pub struct Day {
+    pub year: u32,
+    pub name: &'static str,
+    pub title: &'static str,
+    pub run: fn(&str, &[u32]) -> DayRun,
+    /// Parses the input without preparing or solving it, to tell a bad input from a failing
+    /// part.
+    pub parse: fn(&str),
+    /// Runs the day on an input too big to read into memory whole.
+    pub stream: Option<fn(&InputSource, &[u32]) -> DayRun>,
+    pub frames: Option<fn(&str) -> Frames>,
+    pub explain: Option<fn(&str) -> Trace>,
+    /// Runs every variant of the selected parts, for `compare`.
+    pub compare: Option<RunVariants>,
+    /// Checks the structure of an input without solving it, for `lint`.
+    pub lint: Option<fn(&str) -> Vec<Violation>>,
+    /// Makes a random input of roughly `size` items (lines, instructions or files), for `scale`.
+    pub generate: Option<fn(usize, &mut Rng) -> String>,
+    /// How the input is laid out, so that `minimize` and `fuzz` can shrink it without breaking
+    /// it.
+    pub shape: Shape,
+}
+
+/// Each year's days, oldest first. Helpers shared across years live in `common`.
+const YEARS: &[&[Day]] = &[y2024::DAYS];
+/// Every year with puzzles, whether or not any of its days are enabled.
+pub const YEAR_NUMBERS: &[u32] = &[y2024::YEAR];
+
+impl Day {
+    pub fn number(&self) -> u32 {
+        self.name.trim_start_matches("day").parse().unwrap()
+    }
+
+    /// Names the day across years, e.g. `2024/day6`. Inputs, fixtures and sources are kept
+    /// under the same path.
+    pub fn id(&self) -> String {
+        format!("{}/{}", self.year, self.name)
+    }
+}
+
+/// Every enabled day of every year, in order.
+pub fn days() -> impl Iterator<Item = &'static Day> {
+    YEARS.iter().flat_map(|days| days.iter())
+}
+
+pub fn find_day(year: u32, number: u32) -> Option<&'static Day> {
+    days().find(|day| day.year == year && day.number() == number)
+}
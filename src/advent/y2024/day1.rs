@@ -0,0 +1,272 @@
+use crate::{
+    answer::Answer,
+    input::InputSource,
+    lint::{self, Violation},
+    rng::Rng,
+    solution::{PartResult, Solution, StreamSolution, Variant, Variants},
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    io, iter,
+};
+
+type List = Vec<i32>;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = (List, List);
+    type Prepared = ();
+
+    fn parse(input: &str) -> Self::Input {
+        get_lists(input)
+    }
+
+    fn prepare(_lists: &Self::Input) {}
+
+    fn part1(lists: &Self::Input, _prepared: &()) -> PartResult {
+        Ok(part1(&lists.0, &lists.1))
+    }
+
+    fn part2(lists: &Self::Input, _prepared: &()) -> PartResult {
+        Ok(part2(&lists.0, &lists.1))
+    }
+}
+
+impl Variants for Solver {
+    fn variants() -> Vec<Variant<Self>> {
+        vec![Variant {
+            part: 2,
+            name: "hash_count",
+            solve: |lists, _prepared| Ok(part2_hash_count(&lists.0, &lists.1)),
+        }]
+    }
+}
+
+impl StreamSolution for Solver {
+    fn part1(source: &InputSource) -> PartResult {
+        let (counts1, counts2) = count_lists(source)?;
+        Ok(sorted_distance(&counts1, &counts2).into())
+    }
+
+    fn part2(source: &InputSource) -> PartResult {
+        let (counts1, counts2) = count_lists(source)?;
+        let answer: i128 = counts1
+            .iter()
+            .map(|(n1, count1)| {
+                let count2 = counts2.get(n1).copied().unwrap_or_default();
+                i128::from(*n1) * i128::from(count1 * count2)
+            })
+            .sum();
+        Ok(answer.into())
+    }
+}
+
+/// How many times each number appears in each list.
+type Counts = BTreeMap<i32, u64>;
+
+/// Counts the lists a line at a time, so memory grows with the distinct numbers rather than
+/// the length of the lists.
+fn count_lists(source: &InputSource) -> io::Result<(Counts, Counts)> {
+    let mut counts1 = Counts::new();
+    let mut counts2 = Counts::new();
+    for line in source.lines()? {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (first, second) = parse_line(&line);
+        *counts1.entry(first).or_default() += 1;
+        *counts2.entry(second).or_default() += 1;
+    }
+    Ok((counts1, counts2))
+}
+
+/// The total distance between the lists' numbers paired up in sorted order, walking both
+/// counts at once.
+fn sorted_distance(counts1: &Counts, counts2: &Counts) -> i128 {
+    let mut iter1 = counts1.iter().map(|(&n, &count)| (n, count));
+    let mut iter2 = counts2.iter().map(|(&n, &count)| (n, count));
+    let (mut next1, mut next2) = (iter1.next(), iter2.next());
+    let mut total = 0;
+
+    while let (Some((n1, count1)), Some((n2, count2))) = (next1, next2) {
+        let pairs = count1.min(count2);
+        total += i128::from(n1.abs_diff(n2)) * i128::from(pairs);
+        next1 = if count1 > pairs {
+            Some((n1, count1 - pairs))
+        } else {
+            iter1.next()
+        };
+        next2 = if count2 > pairs {
+            Some((n2, count2 - pairs))
+        } else {
+            iter2.next()
+        };
+    }
+
+    total
+}
+
+fn parse_line(line: &str) -> (i32, i32) {
+    let mut parts = line.split_whitespace();
+    let first = parts.next().unwrap().parse::<i32>().unwrap();
+    let second = parts.next().unwrap().parse::<i32>().unwrap();
+    (first, second)
+}
+
+fn get_lists(input: &str) -> (List, List) {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(parse_line)
+        .unzip()
+}
+
+fn part1(vec1: &List, vec2: &List) -> Answer {
+    let mut vec1 = vec1.clone();
+    let mut vec2 = vec2.clone();
+    vec1.sort();
+    vec2.sort();
+
+    let answer: i32 = iter::zip(vec1, vec2).map(|(n1, n2)| (n1 - n2).abs()).sum();
+    answer.into()
+}
+
+fn part2(vec1: &List, vec2: &List) -> Answer {
+    let answer: i32 = vec1
+        .iter()
+        .map(|n1| {
+            let num_appears: i32 = vec2
+                .iter()
+                .filter(|n2| n1 == *n2)
+                .count()
+                .try_into()
+                .unwrap();
+            n1 * num_appears
+        })
+        .sum();
+    answer.into()
+}
+
+/// Counts the second list once instead of scanning it for each number of the first.
+fn part2_hash_count(vec1: &List, vec2: &List) -> Answer {
+    let mut counts: HashMap<i32, i32> = HashMap::new();
+    for n2 in vec2 {
+        *counts.entry(*n2).or_default() += 1;
+    }
+    let answer: i32 = vec1
+        .iter()
+        .map(|n1| n1 * counts.get(n1).copied().unwrap_or_default())
+        .sum();
+    answer.into()
+}
+
+/// `size` lines of two five-digit location IDs, like the puzzle input.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    (0..size)
+        .map(|_| {
+            format!(
+                "{}   {}\n",
+                rng.range(10000, 100000),
+                rng.range(10000, 100000)
+            )
+        })
+        .collect()
+}
+
+/// Each line is two location IDs.
+pub fn lint(input: &str) -> Vec<Violation> {
+    let mut violations = lint::non_empty(input);
+    violations.extend(lint::each_line(input, |number, line| {
+        match lint::numbers::<i32>(number, line, None) {
+            Ok(ids) if ids.len() == 2 => vec![],
+            Ok(ids) => vec![Violation::line(
+                number,
+                format!("expected 2 location IDs, found {}", ids.len()),
+            )],
+            Err(violations) => violations,
+        }
+    }));
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "3   4
+4   3
+2   5
+1   3
+3   9
+3   3";
+
+    #[test]
+    fn test_part1() {
+        let (vec1, vec2) = get_lists(TEST_INPUT);
+        assert_eq!(part1(&vec1, &vec2), 11);
+    }
+
+    #[test]
+    fn test_part2() {
+        let (vec1, vec2) = get_lists(TEST_INPUT);
+        assert_eq!(part2(&vec1, &vec2), 31);
+        assert_eq!(part2_hash_count(&vec1, &vec2), 31);
+    }
+
+    #[test]
+    fn test_stream() {
+        let source = InputSource::Text(TEST_INPUT.into());
+        assert_eq!(<Solver as StreamSolution>::part1(&source).unwrap(), 11);
+        assert_eq!(<Solver as StreamSolution>::part2(&source).unwrap(), 31);
+    }
+
+    #[test]
+    fn test_degenerate() {
+        // Empty lists are no distance apart, and a lone pair is as far apart as its numbers.
+        let cases = [
+            ("", 0, 0),
+            ("\n", 0, 0),
+            ("3   4", 1, 0),
+            ("3   3\n\n", 0, 3),
+        ];
+        for (input, part1_answer, part2_answer) in cases {
+            let (vec1, vec2) = get_lists(input);
+            assert_eq!(part1(&vec1, &vec2), part1_answer, "{input:?}");
+            assert_eq!(part2(&vec1, &vec2), part2_answer, "{input:?}");
+            let source = InputSource::Text(input.into());
+            assert_eq!(
+                <Solver as StreamSolution>::part1(&source).unwrap(),
+                part1_answer
+            );
+            assert_eq!(
+                <Solver as StreamSolution>::part2(&source).unwrap(),
+                part2_answer
+            );
+        }
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(50, &mut Rng::new(1));
+        let (vec1, vec2) = get_lists(&input);
+        assert_eq!((vec1.len(), vec2.len()), (50, 50));
+    }
+
+    #[test]
+    fn test_lint() {
+        assert!(lint(TEST_INPUT).is_empty());
+        let violations: Vec<String> = lint("3   4\n4\n2   x\n")
+            .iter()
+            .map(Violation::to_string)
+            .collect();
+        assert_eq!(
+            violations,
+            vec![
+                "line 2: expected 2 location IDs, found 1",
+                "line 3: expected a number, found \"x\""
+            ]
+        );
+    }
+}
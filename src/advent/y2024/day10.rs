@@ -0,0 +1,179 @@
+use crate::{
+    advent::common::{Direction, Point},
+    answer::Answer,
+    graph::{bfs, count_paths},
+    lint::{self, Violation},
+    rng::Rng,
+    solution::{PartResult, Solution},
+};
+
+type Map = Vec<Vec<usize>>;
+type Trailheads = Vec<Point>;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Map;
+    type Prepared = Trailheads;
+
+    fn parse(input: &str) -> Self::Input {
+        parse_map(input)
+    }
+
+    fn prepare(map: &Self::Input) -> Self::Prepared {
+        find_trailheads(map)
+    }
+
+    fn part1(map: &Self::Input, trailheads: &Self::Prepared) -> PartResult {
+        Ok(part1(map, trailheads))
+    }
+
+    fn part2(map: &Self::Input, trailheads: &Self::Prepared) -> PartResult {
+        Ok(part2(map, trailheads))
+    }
+}
+
+fn parse_map(input: &str) -> Map {
+    input
+        .lines()
+        .map(|line| {
+            line.chars()
+                .map(|c| c.to_digit(10).unwrap() as usize)
+                .collect()
+        })
+        .collect()
+}
+
+fn find_trailheads(map: &Map) -> Trailheads {
+    let mut trailheads: Trailheads = vec![];
+    for (y, row) in map.iter().enumerate() {
+        for (x, &height) in row.iter().enumerate() {
+            if height == 0 {
+                trailheads.push(Point::new(x as i64, y as i64));
+            }
+        }
+    }
+    trailheads
+}
+
+fn uphill_steps<'a>(map: &'a Map, pos: &Point) -> impl Iterator<Item = Point> + 'a {
+    let next_height = pos.get(map).unwrap() + 1;
+    pos.neighbors(&Direction::CARDINAL)
+        .filter(move |next_pos| next_pos.get(map) == Some(&next_height))
+}
+
+fn is_trailend(map: &Map, pos: &Point) -> bool {
+    pos.get(map) == Some(&9)
+}
+
+fn part1(map: &Map, trailheads: &Trailheads) -> Answer {
+    trailheads
+        .iter()
+        .map(|trailhead| {
+            bfs(*trailhead, |pos| uphill_steps(map, pos))
+                .keys()
+                .filter(|pos| is_trailend(map, pos))
+                .count()
+        })
+        .sum::<usize>()
+        .into()
+}
+
+fn part2(map: &Map, trailheads: &Trailheads) -> Answer {
+    trailheads
+        .iter()
+        .map(|trailhead| {
+            count_paths(
+                *trailhead,
+                |pos| uphill_steps(map, pos),
+                |pos| is_trailend(map, pos),
+            )
+        })
+        .sum::<usize>()
+        .into()
+}
+
+/// A map of `size` rows of 50 heights, as wide as the puzzle input. Most heights are one more
+/// than the height to their left or above, wrapping from 9 to 0, so that there are trails.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    const WIDTH: usize = 50;
+    let mut map: Map = vec![];
+    for y in 0..size {
+        let mut row = vec![];
+        for x in 0..WIDTH {
+            let height = match rng.range(0, 4) {
+                0 | 1 if x > 0 => (row[x - 1] + 1) % 10,
+                2 if y > 0 => (map[y - 1][x] + 1) % 10,
+                _ => rng.range(0, 10) as usize,
+            };
+            row.push(height);
+        }
+        map.push(row);
+    }
+
+    map.iter()
+        .map(|row| row.iter().map(usize::to_string).collect::<String>() + "\n")
+        .collect()
+}
+
+/// The topographic map is a rectangular grid of heights from 0 to 9.
+pub fn lint(input: &str) -> Vec<Violation> {
+    lint::grid(input, |c| c.is_ascii_digit(), "a height")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732";
+
+    #[test]
+    fn test_generate() {
+        let input = generate(40, &mut Rng::new(1));
+        assert!(lint(&input).is_empty());
+        let map = parse_map(&input);
+        assert_eq!((map.len(), map[0].len()), (40, 50));
+        assert_ne!(part1(&map, &find_trailheads(&map)), 0);
+    }
+
+    #[test]
+    fn test_part1() {
+        let map = parse_map(TEST_INPUT);
+        let answer = part1(&map, &find_trailheads(&map));
+        assert_eq!(answer, 36);
+    }
+
+    #[test]
+    fn test_part2() {
+        let map = parse_map(TEST_INPUT);
+        let answer = part2(&map, &find_trailheads(&map));
+        assert_eq!(answer, 81);
+    }
+
+    #[test]
+    fn test_degenerate() {
+        // A trail needs every height from 0 to 9, so only the last map has one.
+        for (input, answer) in [("", 0), ("\n", 0), ("0", 0), ("9", 0), ("0123456789", 1)] {
+            let map = parse_map(input);
+            let trailheads = find_trailheads(&map);
+            assert_eq!(part1(&map, &trailheads), answer, "{input:?}");
+            assert_eq!(part2(&map, &trailheads), answer, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn test_lint() {
+        assert!(lint("0123\n1234\n").is_empty());
+        assert_eq!(
+            lint("0123\n12.4\n"),
+            vec![Violation::at(2, 3, "expected a height, found '.'")]
+        );
+    }
+}
@@ -0,0 +1,354 @@
+use crate::{
+    input::InputSource,
+    lint::{self, Violation},
+    rng::Rng,
+    solution::{self, PartResult, Solution, StreamSolution},
+    trace::Trace,
+};
+
+type LevelReport = Vec<i32>;
+type LevelReports = Vec<LevelReport>;
+type LevelDiffReport = Vec<i32>;
+type LevelDiffReports = Vec<LevelDiffReport>;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = LevelReports;
+    type Prepared = ();
+
+    fn parse(data_string: &str) -> Self::Input {
+        get_levels_from_str(data_string)
+    }
+
+    fn prepare(_levels: &Self::Input) {}
+
+    fn part1(levels: &Self::Input, _prepared: &()) -> PartResult {
+        part1(levels)
+    }
+
+    fn part2(levels: &Self::Input, _prepared: &()) -> PartResult {
+        part2(levels)
+    }
+}
+
+impl StreamSolution for Solver {
+    fn part1(source: &InputSource) -> PartResult {
+        count_safe_reports(source, report_is_safe_pt1)
+    }
+
+    fn part2(source: &InputSource) -> PartResult {
+        count_safe_reports(source, report_is_safe_pt2)
+    }
+}
+
+fn count_safe_reports(source: &InputSource, is_safe: fn(&LevelReport) -> bool) -> PartResult {
+    let mut count = 0;
+    for line in source.lines()? {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if is_safe(&parse_report(&line)) {
+            count += 1;
+        }
+    }
+    Ok(count.into())
+}
+
+fn get_levels_from_str(data_string: &str) -> LevelReports {
+    data_string
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(parse_report)
+        .collect()
+}
+
+fn parse_report(line: &str) -> LevelReport {
+    line.split_whitespace()
+        .map(|s| s.parse::<i32>().unwrap())
+        .collect()
+}
+
+fn levels_to_diffs(level_reports: &LevelReports) -> LevelDiffReports {
+    level_reports.iter().map(level_to_diff).collect()
+}
+
+fn level_to_diff(level_report: &LevelReport) -> LevelDiffReport {
+    level_report
+        .windows(2)
+        .map(|pair| pair[1] - pair[0])
+        .collect()
+}
+
+/// A report of a single level, whether in the input or left after dampening a report of two,
+/// has no changes to be unsafe.
+fn all_increasing_or_decreasing(diff_report: &LevelDiffReport) -> bool {
+    let Some(&first) = diff_report.first() else {
+        return true;
+    };
+    if first < 0 {
+        diff_report.iter().skip(1).all(|&d| d < 0)
+    } else if first > 0 {
+        diff_report.iter().skip(1).all(|&d| d > 0)
+    } else {
+        false
+    }
+}
+
+fn all_in_safe_diff_range(diff_report: &LevelDiffReport) -> bool {
+    let safe_range_positive = 1..=3;
+    diff_report
+        .iter()
+        .all(|d| safe_range_positive.contains(&d.abs()))
+}
+
+fn diff_report_is_safe_pt1(diff_report: &LevelDiffReport) -> bool {
+    let aiod = all_increasing_or_decreasing(diff_report);
+    let aisdr = all_in_safe_diff_range(diff_report);
+    aiod && aisdr
+}
+
+fn report_is_safe_pt1(report: &LevelReport) -> bool {
+    let diff = level_to_diff(report);
+    diff_report_is_safe_pt1(&diff)
+}
+
+fn report_is_safe_pt2(report: &LevelReport) -> bool {
+    if report_is_safe_pt1(report) {
+        return true;
+    }
+
+    for i in 0..report.len() {
+        let mut report = report.clone();
+        report.remove(i);
+        if report_is_safe_pt1(&report) {
+            return true;
+        }
+    }
+
+    false
+}
+
+fn part1(reports: &LevelReports) -> PartResult {
+    let diff_reports = levels_to_diffs(reports);
+    Ok(diff_reports
+        .iter()
+        .filter(|r| diff_report_is_safe_pt1(r))
+        .count()
+        .into())
+}
+
+fn part2(reports: &LevelReports) -> PartResult {
+    Ok(reports
+        .iter()
+        .filter(|r| report_is_safe_pt2(r))
+        .count()
+        .into())
+}
+
+pub fn explain(data_string: &str) -> Trace {
+    let reports = get_levels_from_str(data_string);
+    let unsafe_reports: Vec<(usize, &LevelReport)> = reports
+        .iter()
+        .enumerate()
+        .filter(|(_i, r)| !report_is_safe_pt1(r))
+        .map(|(i, r)| (i + 1, r))
+        .collect();
+    let mut trace = Trace::default();
+
+    let part1_trace = trace.part(1, solution::format_result(&part1(&reports)));
+    for &(line, report) in &unsafe_reports {
+        let reason = unsafe_reason(report).unwrap_or_default();
+        part1_trace.step(
+            Some(line),
+            "unsafe",
+            format!("{} {reason}", format_report(report)),
+        );
+    }
+
+    let part2_trace = trace.part(2, solution::format_result(&part2(&reports)));
+    for &(line, report) in &unsafe_reports {
+        let removable = (0..report.len()).find(|&i| {
+            let mut report = report.clone();
+            report.remove(i);
+            report_is_safe_pt1(&report)
+        });
+        match removable {
+            Some(i) => part2_trace.step(
+                Some(line),
+                "dampened",
+                format!(
+                    "{} is safe after removing level {} at position {}",
+                    format_report(report),
+                    report[i],
+                    i + 1
+                ),
+            ),
+            None => part2_trace.step(
+                Some(line),
+                "unsafe",
+                format!(
+                    "{} is unsafe whichever level is removed",
+                    format_report(report)
+                ),
+            ),
+        }
+    }
+
+    trace
+}
+
+fn format_report(report: &LevelReport) -> String {
+    report
+        .iter()
+        .map(|l| l.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn unsafe_reason(report: &LevelReport) -> Option<String> {
+    let diffs = level_to_diff(report);
+    let first = *diffs.first()?;
+
+    for (i, &diff) in diffs.iter().enumerate() {
+        let (from, to) = (report[i], report[i + 1]);
+        if diff == 0 {
+            return Some(format!("has no change from {from} to {to}"));
+        }
+        if diff.signum() != first.signum() {
+            return Some(format!("changes direction from {from} to {to}"));
+        }
+        if diff.abs() > 3 {
+            return Some(format!("jumps by {} from {from} to {to}", diff.abs()));
+        }
+    }
+
+    None
+}
+
+/// `size` reports of five to eight levels that mostly change gradually, so that some are safe.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        let mut level = rng.range(1, 90) as i64;
+        let direction = *rng.choose(&[-1, 1]);
+        let mut levels = vec![level];
+        for _ in 1..rng.range(5, 9) {
+            let step = match rng.chance(1, 10) {
+                true => rng.range(0, 8) as i64 - 4,
+                false => direction * rng.range(1, 4) as i64,
+            };
+            level = (level + step).max(1);
+            levels.push(level);
+        }
+        let levels: Vec<String> = levels.iter().map(i64::to_string).collect();
+        input += &levels.join(" ");
+        input.push('\n');
+    }
+    input
+}
+
+/// Each line is a report of at least one level. A blank line is skipped rather than read as a
+/// report, so it is most likely a mistake.
+pub fn lint(input: &str) -> Vec<Violation> {
+    let mut violations = lint::non_empty(input);
+    violations.extend(lint::each_line(input, |number, line| {
+        match lint::numbers::<i32>(number, line, None) {
+            Ok(levels) if !levels.is_empty() => vec![],
+            Ok(_) => vec![Violation::line(
+                number,
+                "expected at least 1 level, found 0",
+            )],
+            Err(violations) => violations,
+        }
+    }));
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_reports() -> LevelReports {
+        vec![
+            vec![7, 6, 4, 2, 1],
+            vec![1, 2, 7, 8, 9],
+            vec![9, 7, 6, 2, 1],
+            vec![1, 3, 2, 4, 5],
+            vec![8, 6, 4, 4, 1],
+            vec![1, 3, 6, 7, 9],
+        ]
+    }
+
+    #[test]
+    fn test_part1() {
+        let test_reports = get_test_reports();
+        let answer = part1(&test_reports).unwrap();
+        assert_eq!(answer, 2);
+    }
+
+    #[test]
+    fn test_explain() {
+        let data_string = "7 6 4 2 1\n1 2 7 8 9\n1 3 2 4 5\n";
+        let expected = "Part 1: 1\n  \
+            line 2: unsafe: 1 2 7 8 9 jumps by 5 from 2 to 7\n  \
+            line 3: unsafe: 1 3 2 4 5 changes direction from 3 to 2\n\
+            Part 2: 2\n  \
+            line 2: unsafe: 1 2 7 8 9 is unsafe whichever level is removed\n  \
+            line 3: dampened: 1 3 2 4 5 is safe after removing level 3 at position 2\n";
+        assert_eq!(explain(data_string).to_string(), expected);
+    }
+
+    #[test]
+    fn test_part2() {
+        let test_reports = get_test_reports();
+        let answer = part2(&test_reports).unwrap();
+        assert_eq!(answer, 4);
+    }
+
+    #[test]
+    fn test_degenerate() {
+        // A single level is safe, in the input and after dampening a pair, and a blank line is
+        // not a report at all.
+        let reports = get_levels_from_str("7 6\n5\n\n1 9\n");
+        assert_eq!(reports, vec![vec![7, 6], vec![5], vec![1, 9]]);
+        assert_eq!(part1(&reports).unwrap(), 2);
+        assert_eq!(part2(&reports).unwrap(), 3);
+        let source = InputSource::Text("7 6\n5\n\n1 9\n".into());
+        assert_eq!(<Solver as StreamSolution>::part1(&source).unwrap(), 2);
+        assert_eq!(<Solver as StreamSolution>::part2(&source).unwrap(), 3);
+
+        // No reports, none safe.
+        for input in ["", "\n"] {
+            assert_eq!(part1(&get_levels_from_str(input)).unwrap(), 0);
+            let source = InputSource::Text(input.into());
+            assert_eq!(<Solver as StreamSolution>::part1(&source).unwrap(), 0);
+        }
+    }
+
+    #[test]
+    fn test_stream() {
+        let text: Vec<String> = get_test_reports().iter().map(format_report).collect();
+        let source = InputSource::Text(text.join("\n"));
+        assert_eq!(<Solver as StreamSolution>::part1(&source).unwrap(), 2);
+        assert_eq!(<Solver as StreamSolution>::part2(&source).unwrap(), 4);
+    }
+
+    #[test]
+    fn test_generate() {
+        let reports = get_levels_from_str(&generate(200, &mut Rng::new(1)));
+        assert_eq!(reports.len(), 200);
+        let safe = part1(&reports).unwrap();
+        assert!(safe != 0 && safe != 200);
+    }
+
+    #[test]
+    fn test_lint() {
+        assert!(lint("7 6 4 2 1\n1 2 7 8 9\n").is_empty());
+        assert_eq!(
+            lint("7 6 4 2 1\n5\n\n"),
+            vec![Violation::line(3, "expected at least 1 level, found 0")]
+        );
+    }
+}
@@ -0,0 +1,294 @@
+use crate::{
+    answer::Answer,
+    input::InputSource,
+    lint::{self, Violation},
+    rng::Rng,
+    solution::{PartResult, Solution, StreamSolution},
+    trace::Trace,
+};
+use regex::{bytes, Regex};
+use std::io;
+
+const MUL_PATTERN: &str = r"mul\((\d{1,3}),(\d{1,3})\)";
+const MUL_OR_TOGGLE_PATTERN: &str = r"(mul\((\d{1,3}),(\d{1,3})\)|do\(\)|don't\(\))";
+/// The longest instruction either pattern matches, `mul(123,456)`.
+const LONGEST_MATCH: usize = 12;
+const STREAM_CHUNK: usize = 1 << 20;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = String;
+    type Prepared = ();
+
+    fn parse(data: &str) -> Self::Input {
+        data.to_string()
+    }
+
+    fn prepare(_data: &Self::Input) {}
+
+    fn part1(data: &Self::Input, _prepared: &()) -> PartResult {
+        Ok(part1(data))
+    }
+
+    fn part2(data: &Self::Input, _prepared: &()) -> PartResult {
+        Ok(part2(data))
+    }
+}
+
+impl StreamSolution for Solver {
+    fn part1(source: &InputSource) -> PartResult {
+        Ok(stream_sum(source, STREAM_CHUNK, false)?.into())
+    }
+
+    fn part2(source: &InputSource) -> PartResult {
+        Ok(stream_sum(source, STREAM_CHUNK, true)?.into())
+    }
+}
+
+/// Sums the products of the `mul` instructions in `source`, read in chunks of `chunk_size`
+/// bytes. A match starting within `LONGEST_MATCH - 1` bytes of the end of what has been read
+/// might be cut short, so the unscanned tail is carried over to the next chunk. `toggles`
+/// makes `do()` and `don't()` enable and disable the instructions after them.
+fn stream_sum(source: &InputSource, chunk_size: usize, toggles: bool) -> io::Result<u64> {
+    let regex = bytes::Regex::new(MUL_OR_TOGGLE_PATTERN).unwrap();
+    let number = |bytes: &[u8]| -> u64 { std::str::from_utf8(bytes).unwrap().parse().unwrap() };
+
+    let mut chunks = source.chunks(chunk_size)?.peekable();
+    let mut buffer: Vec<u8> = vec![];
+    let mut mult_enabled = true;
+    let mut sum = 0;
+
+    while let Some(chunk) = chunks.next() {
+        buffer.extend(chunk?);
+        let at_end = chunks.peek().is_none();
+        let safe_len = if at_end {
+            buffer.len()
+        } else {
+            buffer.len().saturating_sub(LONGEST_MATCH - 1)
+        };
+
+        let mut scanned = 0;
+        for captures in regex.captures_iter(&buffer) {
+            let m = captures.get(0).unwrap();
+            if m.start() >= safe_len {
+                break;
+            }
+            scanned = m.end();
+            match m.as_bytes() {
+                b"do()" => mult_enabled = true,
+                b"don't()" => mult_enabled = false,
+                _ if mult_enabled || !toggles => {
+                    sum += number(&captures[2]) * number(&captures[3]);
+                }
+                _ => {}
+            }
+        }
+        buffer.drain(..scanned.max(safe_len));
+    }
+
+    Ok(sum)
+}
+
+fn part1(data: &str) -> Answer {
+    let regex = Regex::new(MUL_PATTERN).unwrap();
+
+    let num_pairs: Vec<(u64, u64)> = regex
+        .captures_iter(data)
+        .map(|captures| {
+            (
+                captures[1].parse::<u64>().unwrap(),
+                captures[2].parse::<u64>().unwrap(),
+            )
+        })
+        .collect();
+
+    let answer: u64 = num_pairs.iter().map(|(x, y)| x * y).sum();
+    answer.into()
+}
+
+fn part2(data: &str) -> Answer {
+    let regex = Regex::new(MUL_OR_TOGGLE_PATTERN).unwrap();
+
+    let mut mult_enabled = true;
+    let mut sum = 0;
+
+    for captures in regex.captures_iter(data) {
+        let matched_str = captures[0].parse::<String>().unwrap();
+        match matched_str.as_str() {
+            m if m.starts_with("mul(") && mult_enabled => {
+                let x = captures[2].parse::<u64>().unwrap();
+                let y = captures[3].parse::<u64>().unwrap();
+                sum += x * y;
+            }
+            "do()" => mult_enabled = true,
+            "don't()" => mult_enabled = false,
+            _ => {}
+        }
+    }
+
+    sum.into()
+}
+
+pub fn explain(data: &str) -> Trace {
+    let line_of = |offset: usize| Some(data[..offset].matches('\n').count() + 1);
+    let mut trace = Trace::default();
+
+    let part1_trace = trace.part(1, part1(data));
+    for captures in Regex::new(MUL_PATTERN).unwrap().captures_iter(data) {
+        let x = captures[1].parse::<u64>().unwrap();
+        let y = captures[2].parse::<u64>().unwrap();
+        let m = captures.get(0).unwrap();
+        part1_trace.step(
+            line_of(m.start()),
+            "mul",
+            format!("{} = {}", m.as_str(), x * y),
+        );
+    }
+
+    let part2_trace = trace.part(2, part2(data));
+    let mut mult_enabled = true;
+    for captures in Regex::new(MUL_OR_TOGGLE_PATTERN)
+        .unwrap()
+        .captures_iter(data)
+    {
+        let m = captures.get(0).unwrap();
+        let line = line_of(m.start());
+        match m.as_str() {
+            "do()" => {
+                mult_enabled = true;
+                part2_trace.step(line, "toggle", "do() enables mul");
+            }
+            "don't()" => {
+                mult_enabled = false;
+                part2_trace.step(line, "toggle", "don't() disables mul");
+            }
+            mul if mult_enabled => {
+                let x = captures[2].parse::<u64>().unwrap();
+                let y = captures[3].parse::<u64>().unwrap();
+                part2_trace.step(line, "mul", format!("{mul} = {}", x * y));
+            }
+            _ => {}
+        }
+    }
+
+    trace
+}
+
+/// The memory is mostly junk, so only its characters are checked: printable ASCII, with at
+/// least one well-formed `mul` instruction somewhere.
+pub fn lint(input: &str) -> Vec<Violation> {
+    let mut violations = lint::non_empty(input);
+    violations.extend(lint::chars(
+        input,
+        |c| c == ' ' || c.is_ascii_graphic(),
+        "printable ASCII",
+    ));
+    if violations.is_empty() && !Regex::new(MUL_PATTERN).unwrap().is_match(input) {
+        violations.push(Violation::input("has no mul instructions"));
+    }
+    violations
+}
+
+/// Corrupted memory with `size` instructions, some of them damaged, separated by junk.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    const JUNK: &[&str] = &[
+        "%", "&", "!", "^", "]", "[", "(", ")", "how()", "select()", " ",
+    ];
+    let mut memory = String::new();
+    for _ in 0..size {
+        for _ in 0..rng.range(0, 4) {
+            memory += *rng.choose(JUNK);
+        }
+        let (x, y) = (rng.range(1, 1000), rng.range(1, 1000));
+        match rng.range(0, 10) {
+            0 => memory += "do()",
+            1 => memory += "don't()",
+            2 => memory += &format!("mul[{x},{y}]"),
+            3 => memory += &format!("mul({x},{y}"),
+            _ => memory += &format!("mul({x},{y})"),
+        }
+    }
+    memory
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_DATA: &str =
+        "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(TEST_DATA), 161);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(TEST_DATA), 48);
+    }
+
+    #[test]
+    fn test_stream_sum() {
+        let source = InputSource::Text(TEST_DATA.into());
+        for chunk_size in 1..=TEST_DATA.len() + 1 {
+            assert_eq!(stream_sum(&source, chunk_size, false).unwrap(), 161);
+            assert_eq!(stream_sum(&source, chunk_size, true).unwrap(), 48);
+        }
+    }
+
+    #[test]
+    fn test_degenerate() {
+        for memory in ["", "x", "don't()", "mul(2,3"] {
+            assert_eq!(part1(memory), 0, "{memory:?}");
+            assert_eq!(part2(memory), 0, "{memory:?}");
+        }
+        assert_eq!(
+            stream_sum(&InputSource::Text("".into()), 7, true).unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_lint() {
+        assert!(lint(TEST_DATA).is_empty());
+        assert!(lint(&generate(20, &mut Rng::new(1))).is_empty());
+        assert_eq!(
+            lint("mul(2,4)\tmul(1,é)\n\u{7}\n"),
+            vec![
+                Violation::at(1, 9, "expected printable ASCII, found '\\t'"),
+                Violation::at(1, 16, "expected printable ASCII, found 'é'"),
+                Violation::at(2, 1, "expected printable ASCII, found '\\u{7}'"),
+            ]
+        );
+        assert_eq!(
+            lint("mul[3,7]")[0].to_string(),
+            "input: has no mul instructions"
+        );
+    }
+
+    #[test]
+    fn test_explain() {
+        let expected = "Part 1: 161\n  \
+            line 1: mul: mul(2,4) = 8\n  \
+            line 1: mul: mul(5,5) = 25\n  \
+            line 1: mul: mul(11,8) = 88\n  \
+            line 1: mul: mul(8,5) = 40\n\
+            Part 2: 48\n  \
+            line 1: mul: mul(2,4) = 8\n  \
+            line 1: toggle: don't() disables mul\n  \
+            line 1: toggle: do() enables mul\n  \
+            line 1: mul: mul(8,5) = 40\n";
+        assert_eq!(explain(TEST_DATA).to_string(), expected);
+    }
+
+    #[test]
+    fn test_generate() {
+        let memory = generate(100, &mut Rng::new(1));
+        let source = InputSource::Text(memory.clone());
+        assert_ne!(part1(&memory), 0);
+        assert_eq!(part1(&memory), stream_sum(&source, 7, false).unwrap());
+        assert_eq!(part2(&memory), stream_sum(&source, 7, true).unwrap());
+    }
+}
@@ -0,0 +1,270 @@
+use crate::{
+    answer::Answer,
+    lint::{self, Violation},
+    rng::Rng,
+    solution::{PartResult, Solution},
+};
+use regex::Regex;
+
+type DataLine = Vec<char>;
+type DataLines = Vec<DataLine>;
+type Data = Vec<DataLine>;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Data;
+    type Prepared = ();
+
+    fn parse(input: &str) -> Self::Input {
+        string_to_2d_vec(input)
+    }
+
+    fn prepare(_data: &Self::Input) {}
+
+    fn part1(data: &Self::Input, _prepared: &()) -> PartResult {
+        Ok(part1(data))
+    }
+
+    fn part2(data: &Self::Input, _prepared: &()) -> PartResult {
+        Ok(part2(data))
+    }
+}
+
+/// The width of the grid, which is its widest row, or 0 for an empty input.
+fn num_cols(data: &Data) -> usize {
+    data.iter().map(Vec::len).max().unwrap_or(0)
+}
+
+/// The letter at a point, or a blank past the end of a short row so no word runs through it.
+fn cell(data: &Data, row: usize, col: usize) -> char {
+    data.get(row)
+        .and_then(|r| r.get(col))
+        .copied()
+        .unwrap_or(' ')
+}
+
+fn string_to_2d_vec(input: &str) -> Data {
+    input.lines().map(|line| line.chars().collect()).collect()
+}
+
+fn get_horizontals(data: &Data) -> DataLines {
+    let mut horizs = vec![];
+
+    for row in data {
+        horizs.push(row.clone());
+        horizs.push(row.iter().cloned().rev().collect());
+    }
+
+    horizs
+}
+
+fn get_verticals(data: &Data) -> DataLines {
+    let mut verts = vec![];
+    let num_cols = num_cols(data);
+
+    for x in 0..num_cols {
+        let mut v_line = vec![];
+        for row in 0..data.len() {
+            v_line.push(cell(data, row, x));
+        }
+        verts.push(v_line.clone());
+        verts.push(v_line.iter().cloned().rev().collect());
+    }
+
+    verts
+}
+
+fn get_diagonals(data: &Data) -> DataLines {
+    let mut diagonals = vec![];
+    let num_rows: i32 = data.len().try_into().unwrap();
+    let num_cols: i32 = num_cols(data).try_into().unwrap();
+
+    // Top-left to bottom-right diagonals
+    for d in 0..(num_rows + num_cols - 1) {
+        let mut diagonal = Vec::new();
+        for i in 0..=d {
+            let row = i;
+            let col = d - i;
+            if row < num_rows && col < num_cols {
+                diagonal.push(cell(data, row as usize, col as usize));
+            }
+        }
+        if !diagonal.is_empty() {
+            diagonals.push(diagonal.clone());
+            diagonals.push(diagonal.iter().cloned().rev().collect())
+        }
+    }
+
+    // Top-right to bottom-left diagonals
+    for d in 0..(num_rows + num_cols - 1) {
+        let mut diagonal = Vec::new();
+        for i in 0..=d {
+            let row = i;
+            let col = num_cols - 1 - (d - i);
+            if row < num_rows && col < num_cols && col >= 0 {
+                diagonal.push(cell(data, row as usize, col as usize));
+            }
+        }
+        if !diagonal.is_empty() {
+            diagonals.push(diagonal.clone());
+            diagonals.push(diagonal.iter().cloned().rev().collect())
+        }
+    }
+
+    diagonals
+}
+
+fn count_occurences(line: &DataLine) -> u64 {
+    let line_str: String = line.iter().collect();
+    let regex = Regex::new(r"XMAS").unwrap();
+    regex.find_iter(&line_str).count().try_into().unwrap()
+}
+
+fn pattern_at_point(data: &Data, row: usize, col: usize) -> bool {
+    let tlbr: String = [
+        cell(data, row, col),
+        cell(data, row + 1, col + 1),
+        cell(data, row + 2, col + 2),
+    ]
+    .iter()
+    .collect();
+
+    let trbl: String = [
+        cell(data, row, col + 2),
+        cell(data, row + 1, col + 1),
+        cell(data, row + 2, col),
+    ]
+    .iter()
+    .collect();
+
+    let tlbr_contains = tlbr == "MAS" || tlbr == "SAM";
+    let trbl_contains = trbl == "MAS" || trbl == "SAM";
+    tlbr_contains && trbl_contains
+}
+
+fn part1(data: &Data) -> Answer {
+    let horizontals = get_horizontals(data);
+    let verticals = get_verticals(data);
+    let diagonals = get_diagonals(data);
+    let all_paths = [horizontals, verticals, diagonals].concat();
+
+    all_paths.iter().map(count_occurences).sum::<u64>().into()
+}
+
+fn part2(data: &Data) -> Answer {
+    let num_rows = data.len();
+    let num_cols = num_cols(data);
+    let mut num_occurences = 0u64;
+
+    // An X needs a 3×3 square, so smaller grids have none.
+    for row in 0..num_rows.saturating_sub(2) {
+        for col in 0..num_cols.saturating_sub(2) {
+            if pattern_at_point(data, row, col) {
+                num_occurences += 1;
+            }
+        }
+    }
+
+    num_occurences.into()
+}
+
+/// A word search of `size` rows of 140 letters from `XMAS`, as wide as the puzzle input.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    const WIDTH: usize = 140;
+    let mut grid = String::new();
+    for _ in 0..size {
+        grid.extend((0..WIDTH).map(|_| *rng.choose(&['X', 'M', 'A', 'S'])));
+        grid.push('\n');
+    }
+    grid
+}
+
+/// The word search is a rectangular grid of letters.
+pub fn lint(input: &str) -> Vec<Violation> {
+    lint::grid(input, |c| c.is_ascii_alphabetic(), "a letter")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate() {
+        let input = generate(30, &mut Rng::new(1));
+        assert!(lint(&input).is_empty());
+        let data = string_to_2d_vec(&input);
+        assert_eq!((data.len(), num_cols(&data)), (30, 140));
+        assert_ne!(part1(&data), 0);
+        assert_ne!(part2(&data), 0);
+    }
+
+    #[test]
+    fn test_part1() {
+        let data = string_to_2d_vec(
+            "MMMSXXMASM\n\
+             MSAMXMSMSA\n\
+             AMXSXMAAMM\n\
+             MSAMASMSMX\n\
+             XMASAMXAMM\n\
+             XXAMMXXAMA\n\
+             SMSMSASXSS\n\
+             SAXAMASAAA\n\
+             MAMMMXMMMM\n\
+             MXMXAXMASX\n",
+        );
+        assert_eq!(part1(&data), 18);
+    }
+
+    #[test]
+    fn test_part2() {
+        let data = string_to_2d_vec(
+            ".M.S......\n\
+             ..A..MSMS.\n\
+             .M.S.MAA..\n\
+             ..A.ASMSM.\n\
+             .M.S.M....\n\
+             ..........\n\
+             S.S.S.S.S.\n\
+             .A.A.A.A..\n\
+             M.M.M.M.M.\n\
+             ..........\n",
+        );
+        assert_eq!(part2(&data), 9);
+    }
+
+    #[test]
+    fn test_degenerate() {
+        // Grids too small for a word or an X, or an empty input, have none, and a ragged grid
+        // only has the words that fit in its rows.
+        let cases = [
+            ("", 0, 0),
+            ("\n", 0, 0),
+            (".\n\n", 0, 0),
+            ("XMAS\nM\nA\nSAMX", 3, 0),
+            ("M.S\n.A\nM.S", 0, 1),
+            ("X\n\nM\nA\nS", 0, 0),
+            ("X", 0, 0),
+            ("XMAS", 1, 0),
+            ("X\nM\nA\nS", 1, 0),
+            ("M.S\n.A.", 0, 0),
+        ];
+        for (input, part1_answer, part2_answer) in cases {
+            let data = string_to_2d_vec(input);
+            assert_eq!(part1(&data), part1_answer, "{input:?}");
+            assert_eq!(part2(&data), part2_answer, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn test_lint() {
+        assert!(lint("XMAS\nSAMX\n").is_empty());
+        assert_eq!(
+            lint("XMAS\nSAM\nXM4S\n"),
+            vec![
+                Violation::line(2, "row is 3 wide, but the first row is 4"),
+                Violation::at(3, 3, "expected a letter, found '4'"),
+            ]
+        );
+    }
+}
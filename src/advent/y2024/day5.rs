@@ -0,0 +1,385 @@
+use crate::{
+    graph::{topo_sort, AdjacencyList},
+    lint::{self, Violation},
+    rng::Rng,
+    solution::{self, PartResult, Solution, SolveError},
+    trace::Trace,
+};
+
+type Page = u32;
+type Rule = (Page, Page);
+type Rules = Vec<Rule>;
+/// Edges point from each page to the pages that must come after it.
+type Graph = AdjacencyList<Page>;
+type Update = Vec<Page>;
+type Data = (Vec<Rule>, Vec<Update>);
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Data;
+    type Prepared = Graph;
+
+    fn parse(data_str: &str) -> Self::Input {
+        get_data_from_str(data_str.into())
+    }
+
+    fn prepare((rules, _updates): &Self::Input) -> Self::Prepared {
+        build_graph(rules)
+    }
+
+    fn part1(data: &Self::Input, graph: &Self::Prepared) -> PartResult {
+        part1(data, graph)
+    }
+
+    fn part2(data: &Self::Input, graph: &Self::Prepared) -> PartResult {
+        part2(data, graph)
+    }
+}
+
+/// Without a blank line the whole input is rules, with no updates.
+fn get_data_from_str(data_str: String) -> Data {
+    let (rules_str, updates_str) = data_str
+        .split_once("\n\n")
+        .unwrap_or((data_str.trim_end(), ""));
+
+    let rules = rules_str
+        .lines()
+        .map(|line| {
+            let nums: Vec<Page> = line.split('|').map(|x| x.parse().unwrap()).collect();
+            (nums[0], nums[1])
+        })
+        .collect();
+
+    let updates = updates_str
+        .lines()
+        .map(|line| line.split(',').map(|x| x.parse().unwrap()).collect())
+        .collect();
+
+    (rules, updates)
+}
+
+fn build_graph(rules: &Rules) -> Graph {
+    rules.iter().cloned().collect()
+}
+
+fn update_is_correctly_ordered(graph: &Graph, update: &Update) -> bool {
+    update.iter().enumerate().all(|(i, page)| {
+        update[..i]
+            .iter()
+            .all(|earlier_page| !graph.has_edge(page, earlier_page))
+    })
+}
+
+/// An update that repeats a page has no single order or middle page, so it is rejected rather
+/// than sorted with the repeats dropped.
+fn check_update(update: &Update) -> Result<(), SolveError> {
+    match update
+        .iter()
+        .enumerate()
+        .find(|&(i, page)| update[..i].contains(page))
+    {
+        Some((_, page)) => Err(SolveError::RepeatedPage(format!(
+            "{page} in update {}",
+            format_pages(update)
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Orders the update by the rules, which fails if the rules between its pages form a cycle.
+fn sort_update(graph: &Graph, update: &Update) -> Result<Update, SolveError> {
+    topo_sort(update, |page| graph.successors(page).iter().copied())
+        .map_err(|cycle| SolveError::Cycle(format!("{cycle} in update {}", format_pages(update))))
+}
+
+fn part1((_rules, updates): &Data, graph: &Graph) -> PartResult {
+    let mut sum_of_middle_pages = 0u64;
+
+    for update in updates {
+        check_update(update)?;
+        if update_is_correctly_ordered(graph, update) {
+            let middle_page = update[update.len() / 2];
+            sum_of_middle_pages += middle_page as u64;
+        }
+    }
+
+    Ok(sum_of_middle_pages.into())
+}
+
+fn part2((_rules, updates): &Data, graph: &Graph) -> PartResult {
+    let mut sum_of_middle_pages = 0u64;
+
+    for update in updates {
+        check_update(update)?;
+        if !update_is_correctly_ordered(graph, update) {
+            let sorted_update = sort_update(graph, update)?;
+            let middle_page = sorted_update[sorted_update.len() / 2];
+            sum_of_middle_pages += middle_page as u64;
+        }
+    }
+
+    Ok(sum_of_middle_pages.into())
+}
+
+fn violated_rules(rules: &Rules, update: &Update) -> Rules {
+    let position = |page: Page| update.iter().position(|&p| p == page);
+
+    rules
+        .iter()
+        .filter(|&&(a, b)| match (position(a), position(b)) {
+            (Some(a_pos), Some(b_pos)) => b_pos < a_pos,
+            _ => false,
+        })
+        .cloned()
+        .collect()
+}
+
+fn format_pages(pages: &[Page]) -> String {
+    pages
+        .iter()
+        .map(|p| p.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+pub fn explain(data_str: &str) -> Trace {
+    let data = get_data_from_str(data_str.into());
+    let (rules, updates) = &data;
+    let graph = build_graph(rules);
+    let first_update_line = rules.len() + 2;
+    let mut trace = Trace::default();
+
+    let part1_trace = trace.part(1, solution::format_result(&part1(&data, &graph)));
+    for (i, update) in updates.iter().enumerate() {
+        let violated: Vec<String> = violated_rules(rules, update)
+            .iter()
+            .map(|(a, b)| format!("{a}|{b}"))
+            .collect();
+        if !violated.is_empty() {
+            part1_trace.step(
+                Some(first_update_line + i),
+                "violation",
+                format!("{} breaks {}", format_pages(update), violated.join(", ")),
+            );
+        }
+    }
+
+    let part2_trace = trace.part(2, solution::format_result(&part2(&data, &graph)));
+    for (i, update) in updates.iter().enumerate() {
+        if !update_is_correctly_ordered(&graph, update) {
+            let Ok(sorted_update) = sort_update(&graph, update) else {
+                continue;
+            };
+            part2_trace.step(
+                Some(first_update_line + i),
+                "reordered",
+                format!(
+                    "{} becomes {}, middle page {}",
+                    format_pages(update),
+                    format_pages(&sorted_update),
+                    sorted_update[sorted_update.len() / 2]
+                ),
+            );
+        }
+    }
+
+    trace
+}
+
+/// Rules ordering every pair of 49 two-digit pages, as in the puzzle input, then `size` updates
+/// of five to 23 of those pages. About half of the updates are in order.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut pages: Vec<Page> = (10..100).collect();
+    rng.shuffle(&mut pages);
+    pages.truncate(49);
+
+    let mut input = String::new();
+    for (i, before) in pages.iter().enumerate() {
+        for after in &pages[i + 1..] {
+            input += &format!("{before}|{after}\n");
+        }
+    }
+    input.push('\n');
+
+    for _ in 0..size {
+        let mut update = pages.clone();
+        rng.shuffle(&mut update);
+        update.truncate(rng.range(2, 12) as usize * 2 + 1);
+        if rng.chance(1, 2) {
+            update.sort_by_key(|page| pages.iter().position(|p| p == page));
+        }
+        input += &format_pages(&update);
+        input.push('\n');
+    }
+    input
+}
+
+/// Rules of two pages, a blank line, then updates with an odd number of distinct pages so that
+/// each has a middle page.
+pub fn lint(input: &str) -> Vec<Violation> {
+    let mut violations = lint::non_empty(input);
+    let Some(blank) = lint::numbered_lines(input).find(|(_, line)| line.is_empty()) else {
+        violations.push(Violation::input(
+            "expected a blank line between the rules and the updates",
+        ));
+        return violations;
+    };
+
+    violations.extend(lint::each_line(input, |number, line| {
+        if number < blank.0 {
+            match lint::numbers::<Page>(number, line, Some('|')) {
+                Ok(pages) if pages.len() == 2 => vec![],
+                Ok(_) => vec![Violation::line(number, "expected a rule `X|Y`")],
+                Err(violations) => violations,
+            }
+        } else if number > blank.0 {
+            match lint::numbers::<Page>(number, line, Some(',')) {
+                Ok(pages) if check_update(&pages).is_err() => {
+                    vec![Violation::line(number, "update repeats a page")]
+                }
+                Ok(pages) if pages.len() % 2 == 1 => vec![],
+                Ok(pages) => vec![Violation::line(
+                    number,
+                    format!("update has {} pages, so no middle page", pages.len()),
+                )],
+                Err(violations) => violations,
+            }
+        } else {
+            vec![]
+        }
+    }));
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_DATA_STR: &str = "47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47";
+
+    #[test]
+    fn test_part1() {
+        let data = get_data_from_str(TEST_DATA_STR.into());
+        let answer = part1(&data, &build_graph(&data.0)).unwrap();
+        assert_eq!(answer, 143)
+    }
+
+    #[test]
+    fn test_explain() {
+        let explanation = explain(TEST_DATA_STR).to_string();
+        assert!(explanation.contains("line 26: violation: 75,97,47,61,53 breaks 97|75\n"));
+        assert!(explanation.contains("line 27: violation: 61,13,29 breaks 29|13\n"));
+        assert!(explanation.contains(
+            "line 28: reordered: 97,13,75,29,47 becomes 97,75,47,29,13, middle page 47\n"
+        ));
+        assert_eq!(explanation.matches("violation").count(), 3);
+    }
+
+    #[test]
+    fn test_sort_update_cycle() {
+        let data = get_data_from_str("1|2\n2|3\n3|1\n\n1,2,3".into());
+        let graph = build_graph(&data.0);
+        let error = SolveError::Cycle("1 -> 2 -> 3 -> 1 in update 1,2,3".into());
+        assert_eq!(part2(&data, &graph), Err(error));
+        assert_eq!(
+            part2(&data, &graph).unwrap_err().to_string(),
+            "Rules form a cycle: 1 -> 2 -> 3 -> 1 in update 1,2,3"
+        );
+    }
+
+    #[test]
+    fn test_repeated_page() {
+        // Sorting would drop the repeat and move the middle page, so neither part answers.
+        let data = get_data_from_str("1|2\n\n2,1,2\n".into());
+        let graph = build_graph(&data.0);
+        let error = SolveError::RepeatedPage("2 in update 2,1,2".into());
+        assert_eq!(part1(&data, &graph), Err(error.clone()));
+        assert_eq!(part2(&data, &graph), Err(error));
+        assert_eq!(
+            lint("1|2\n\n2,1,2\n"),
+            vec![Violation::line(3, "update repeats a page")]
+        );
+    }
+
+    #[test]
+    fn test_degenerate() {
+        // A single page is its own middle page, and is in order whatever the rules.
+        let cases = [
+            ("", 0, 0),
+            ("\n", 0, 0),
+            ("1|2", 0, 0),
+            ("1|2\n\n", 0, 0),
+            ("\n\n1", 1, 0),
+            ("1|2\n\n2,1\n1", 1, 2),
+        ];
+        for (input, part1_answer, part2_answer) in cases {
+            let data = get_data_from_str(input.into());
+            let graph = build_graph(&data.0);
+            assert_eq!(part1(&data, &graph).unwrap(), part1_answer, "{input:?}");
+            assert_eq!(part2(&data, &graph).unwrap(), part2_answer, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn test_part2() {
+        let data = get_data_from_str(TEST_DATA_STR.into());
+        let answer = part2(&data, &build_graph(&data.0)).unwrap();
+        assert_eq!(answer, 123)
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(100, &mut Rng::new(1));
+        assert!(lint(&input).is_empty());
+        let data = get_data_from_str(input);
+        assert_eq!((data.0.len(), data.1.len()), (49 * 48 / 2, 100));
+        let graph = build_graph(&data.0);
+        assert_ne!(part1(&data, &graph).unwrap(), 0);
+        assert_ne!(part2(&data, &graph).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_lint() {
+        assert!(lint("47|53\n97|13\n\n75,47,61\n").is_empty());
+        assert_eq!(
+            lint("47|53\n97\n\n75,47\n75,x,61\n"),
+            vec![
+                Violation::line(2, "expected a rule `X|Y`"),
+                Violation::line(4, "update has 2 pages, so no middle page"),
+                Violation::line(5, "expected a number, found \"x\""),
+            ]
+        );
+        assert_eq!(
+            lint("47|53\n")[0].to_string(),
+            "input: expected a blank line between the rules and the updates"
+        );
+    }
+}
@@ -0,0 +1,408 @@
+use crate::{
+    advent::common::{Direction, Point},
+    lint::{self, Violation},
+    rng::Rng,
+    solution::{PartResult, Solution, SolveError},
+};
+use rayon::prelude::*;
+use rustc_hash::FxHashMap;
+use std::iter;
+
+type Obstacles = Vec<Vec<bool>>;
+/// The guard is `None` on a map without one.
+type Data = (Obstacles, Option<Point>, Direction);
+type VisitedDirections = FxHashMap<Point, Direction>;
+
+#[derive(Clone, Debug)]
+struct State {
+    obstacles: Obstacles,
+    guard_pos: Point,
+    guard_dir: Direction,
+    visited_dirs: VisitedDirections,
+}
+
+enum NextStateResult {
+    Next(State),
+    Finish(State),
+    Loop,
+}
+
+fn obstacles_to_strs(obstacles: &Obstacles) -> Vec<Vec<String>> {
+    let mut strs = vec![];
+    for row in obstacles {
+        let mut row_strs = vec![];
+        for cell in row {
+            if *cell {
+                row_strs.push(" # ".into());
+            } else {
+                row_strs.push(" . ".into());
+            }
+        }
+        strs.push(row_strs);
+    }
+    strs
+}
+
+impl std::fmt::Display for State {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut state_strs = obstacles_to_strs(&self.obstacles);
+
+        for pos in self.visited_dirs.keys() {
+            state_strs[pos.y as usize][pos.x as usize] = " X ".into();
+        }
+
+        let guard = self.guard_pos;
+        state_strs[guard.y as usize][guard.x as usize] = format!(" {} ", self.guard_dir.arrow());
+
+        for row in &state_strs {
+            for s in row {
+                write!(f, "{s}",)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Data;
+    /// The guard's walk on the unmodified map, or `None` if it loops.
+    type Prepared = Option<VisitedDirections>;
+
+    fn parse(data_str: &str) -> Self::Input {
+        get_data_from_str(data_str.into())
+    }
+
+    fn prepare(data: &Self::Input) -> Self::Prepared {
+        guard_walk(data)
+    }
+
+    fn part1(_data: &Self::Input, original_path: &Self::Prepared) -> PartResult {
+        part1(original_path)
+    }
+
+    fn part2(data: &Self::Input, original_path: &Self::Prepared) -> PartResult {
+        part2(data, original_path)
+    }
+}
+
+fn get_data_from_str(data_str: String) -> Data {
+    let mut guard_pos = None;
+    let mut guard_dir = Direction::North;
+
+    let obstacles: Obstacles = data_str
+        .lines()
+        .enumerate()
+        .map(|(y, line)| {
+            line.chars()
+                .enumerate()
+                .map(|(x, c)| match c {
+                    '#' => true,
+                    '^' | '>' | 'v' | '<' => {
+                        guard_pos = Some(Point::new(x as i64, y as i64));
+                        guard_dir = Direction::try_from(c).unwrap();
+                        false
+                    }
+                    _ => false,
+                })
+                .collect()
+        })
+        .collect();
+
+    (obstacles, guard_pos, guard_dir)
+}
+
+fn get_next_pos(obstacles: &Obstacles, pos: &Point, dir: &Direction) -> Option<Point> {
+    let next_pos = *pos + dir.offset();
+    next_pos.get(obstacles).map(|_| next_pos)
+}
+
+fn has_obstacle(obstacles: &Obstacles, pos: &Point) -> bool {
+    pos.get(obstacles) == Some(&true)
+}
+
+fn get_next_state(starting_state: &State) -> NextStateResult {
+    let mut state = starting_state.clone();
+
+    loop {
+        let visited = state.visited_dirs.get(&state.guard_pos);
+        match visited {
+            Some(last_dir) => {
+                if *last_dir == state.guard_dir {
+                    return NextStateResult::Loop;
+                }
+            }
+            None => {
+                state.visited_dirs.insert(state.guard_pos, state.guard_dir);
+            }
+        }
+
+        let next_pos_result = get_next_pos(&state.obstacles, &state.guard_pos, &state.guard_dir);
+
+        if next_pos_result.is_none() {
+            return NextStateResult::Finish(state);
+        }
+
+        let next_pos = next_pos_result.unwrap();
+
+        if has_obstacle(&state.obstacles, &next_pos) {
+            state.guard_dir = state.guard_dir.turn_right();
+            continue;
+        }
+
+        state.guard_pos = next_pos;
+        return NextStateResult::Next(state);
+    }
+}
+
+fn initial_state((obstacles, guard_pos, guard_dir): &Data) -> Option<State> {
+    Some(State {
+        obstacles: obstacles.clone(),
+        guard_pos: (*guard_pos)?,
+        guard_dir: *guard_dir,
+        visited_dirs: FxHashMap::default(),
+    })
+}
+
+/// Without a guard nothing is visited.
+fn guard_walk(data: &Data) -> Option<VisitedDirections> {
+    let Some(mut state) = initial_state(data) else {
+        return Some(VisitedDirections::default());
+    };
+
+    loop {
+        let next_state = get_next_state(&state);
+        match next_state {
+            NextStateResult::Next(next_state) => {
+                state = next_state;
+            }
+            NextStateResult::Finish(last_state) => {
+                state = last_state;
+                break;
+            }
+            NextStateResult::Loop => return None,
+        }
+    }
+
+    Some(state.visited_dirs)
+}
+
+pub fn frames(input_str: &str) -> impl Iterator<Item = String> {
+    let data = get_data_from_str(input_str.into());
+
+    iter::successors(initial_state(&data), |state| match get_next_state(state) {
+        NextStateResult::Next(next_state) => Some(next_state),
+        NextStateResult::Finish(_) | NextStateResult::Loop => None,
+    })
+    .map(|state| state.to_string())
+}
+
+fn part1(original_path: &Option<VisitedDirections>) -> PartResult {
+    match original_path {
+        Some(visited_dirs) => Ok(visited_dirs.len().into()),
+        None => Err(SolveError::GuardLoops),
+    }
+}
+
+fn part2(
+    (obstacles, guard_pos, guard_dir): &Data,
+    original_path: &Option<VisitedDirections>,
+) -> PartResult {
+    let original_path = original_path.as_ref().ok_or(SolveError::GuardLoops)?;
+
+    Ok(original_path
+        .keys()
+        .collect::<Vec<_>>()
+        .par_iter()
+        .filter(|pos| {
+            if has_obstacle(obstacles, pos) {
+                return false;
+            }
+            if Some(**pos) == guard_pos.as_ref() {
+                return false;
+            }
+
+            let mut new_obstacles = obstacles.clone();
+            new_obstacles[pos.y as usize][pos.x as usize] = true;
+
+            let walk = guard_walk(&(new_obstacles, *guard_pos, *guard_dir));
+            if walk.is_none() {
+                return true;
+            }
+
+            false
+        })
+        .count()
+        .into())
+}
+
+/// A map of `size` rows of 130 cells, as wide as the puzzle input, with about one obstacle in
+/// 20 cells. The guard starts at the bottom of a clear column and faces up it to an obstacle in
+/// the top row, then turns right along a clear second row and leaves. So part 1 always has an
+/// answer and the path grows with the map, without drawing maps until one works.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    const WIDTH: usize = 130;
+    if size == 0 {
+        return String::new();
+    }
+
+    let mut cells: Vec<Vec<char>> = (0..size)
+        .map(|_| {
+            (0..WIDTH)
+                .map(|_| if rng.chance(1, 20) { '#' } else { '.' })
+                .collect()
+        })
+        .collect();
+    let x = rng.range(0, WIDTH as u64) as usize;
+    for row in &mut cells {
+        row[x] = '.';
+    }
+    if size >= 2 {
+        cells[0][x] = '#';
+        cells[1][x..].fill('.');
+    }
+    cells[size - 1][x] = '^';
+
+    cells
+        .iter()
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect()
+}
+
+/// The map is a rectangular grid of floor and obstacles with exactly one guard, an arrow
+/// showing which way it faces.
+pub fn lint(input: &str) -> Vec<Violation> {
+    let mut violations = lint::grid(input, |c| ".#^>v<".contains(c), "'.', '#' or a guard");
+    let guards: Vec<(usize, usize)> = lint::numbered_lines(input)
+        .flat_map(|(number, line)| {
+            line.chars()
+                .zip(1..)
+                .filter(|(c, _)| "^>v<".contains(*c))
+                .map(move |(_, column)| (number, column))
+        })
+        .collect();
+    match guards.as_slice() {
+        [] if !input.trim().is_empty() => violations.push(Violation::input("has no guard")),
+        [_first, others @ ..] => violations.extend(
+            others
+                .iter()
+                .map(|&(line, column)| Violation::at(line, column, "a second guard")),
+        ),
+        _ => {}
+    }
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{scale, snapshot::assert_snapshot};
+    use std::time::{Duration, Instant};
+
+    const TEST_DATA_STR: &str = "....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...";
+
+    #[test]
+    fn test_part1() {
+        let data = get_data_from_str(TEST_DATA_STR.into());
+        let answer = part1(&guard_walk(&data)).unwrap();
+        assert_eq!(answer, 41)
+    }
+
+    #[test]
+    fn test_part2() {
+        let data = get_data_from_str(TEST_DATA_STR.into());
+        let answer = part2(&data, &guard_walk(&data)).unwrap();
+        assert_eq!(answer, 6)
+    }
+
+    #[test]
+    fn test_guard_directions() {
+        // The same map turned a quarter each time, with the guard turned to match.
+        for (input, guard_dir) in [
+            ("#..\n..#\n^..", Direction::North),
+            (">.#\n...\n.#.", Direction::East),
+            ("..v\n#..\n..#", Direction::South),
+            (".#.\n...\n#.<", Direction::West),
+        ] {
+            let data = get_data_from_str(input.into());
+            assert_eq!(data.2, guard_dir, "{input:?}");
+            assert_eq!(part1(&guard_walk(&data)).unwrap(), 4, "{input:?}");
+            assert!(lint(input).is_empty(), "{input:?}");
+        }
+    }
+
+    #[test]
+    fn test_guard_loops() {
+        let data = get_data_from_str(".#..\n.^.#\n#...\n..#.".into());
+        let original_path = guard_walk(&data);
+        assert_eq!(part1(&original_path), Err(SolveError::GuardLoops));
+        assert_eq!(part2(&data, &original_path), Err(SolveError::GuardLoops));
+    }
+
+    #[test]
+    fn test_degenerate() {
+        // Without a guard nothing is visited, and a guard alone visits only where it stands.
+        for (input, part1_answer) in [("", 0), ("\n", 0), (".", 0), ("#", 0), ("^", 1)] {
+            let data = get_data_from_str(input.into());
+            let original_path = guard_walk(&data);
+            assert_eq!(part1(&original_path).unwrap(), part1_answer, "{input:?}");
+            assert_eq!(part2(&data, &original_path).unwrap(), 0, "{input:?}");
+            assert_eq!(frames(input).count(), part1_answer, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let frames: Vec<String> = frames(TEST_DATA_STR).collect();
+        assert_snapshot("day6_start", &frames[0]);
+        assert_snapshot("day6_turn", &frames[6]);
+        assert_snapshot("day6_end", frames.last().unwrap());
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(40, &mut Rng::new(1));
+        assert!(lint(&input).is_empty());
+        let data = get_data_from_str(input);
+        assert_eq!((data.0.len(), data.0[0].len()), (40, 130));
+        assert_ne!(part1(&guard_walk(&data)).unwrap(), 0);
+
+        // Every size scale uses by default is made quickly, with a path as long as the map.
+        let started = Instant::now();
+        for size in scale::sizes(scale::DEFAULT_MIN_SIZE, scale::DEFAULT_STEPS) {
+            generate(size, &mut Rng::new(1));
+        }
+        assert!(started.elapsed() < Duration::from_secs(10));
+        let data = get_data_from_str(generate(scale::DEFAULT_MIN_SIZE, &mut Rng::new(1)));
+        assert!(guard_walk(&data).unwrap().len() >= scale::DEFAULT_MIN_SIZE - 1);
+        for size in [1, 2] {
+            let data = get_data_from_str(generate(size, &mut Rng::new(1)));
+            assert_ne!(part1(&guard_walk(&data)).unwrap(), 0);
+        }
+    }
+
+    #[test]
+    fn test_lint() {
+        assert!(lint(TEST_DATA_STR).is_empty());
+        assert_eq!(
+            lint("..#\n^.x\n.>.\n"),
+            vec![
+                Violation::at(2, 3, "expected '.', '#' or a guard, found 'x'"),
+                Violation::at(3, 2, "a second guard"),
+            ]
+        );
+        assert_eq!(lint("..#\n")[0].to_string(), "input: has no guard");
+    }
+}
@@ -0,0 +1,473 @@
+use crate::{
+    answer::Answer,
+    input::InputSource,
+    lint::{self, Violation},
+    rng::Rng,
+    solution::{PartResult, Solution, StreamSolution, Variant, Variants},
+    trace::Trace,
+};
+use itertools::Itertools;
+use rayon::prelude::*;
+use std::io;
+
+type Num = usize;
+type Lhs = Num;
+type Rhs = Vec<Num>;
+type UnfinishedEquation = (Lhs, Rhs);
+type Input = Vec<UnfinishedEquation>;
+type Operator = String;
+type Operators = Vec<Operator>;
+
+/// How many equations are read from a stream before they are checked in parallel.
+const STREAM_BATCH: usize = 4096;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Input;
+    type Prepared = ();
+
+    fn parse(input_str: &str) -> Self::Input {
+        get_input_from_str(input_str.into())
+    }
+
+    fn prepare(_input: &Self::Input) {}
+
+    fn part1(input: &Self::Input, _prepared: &()) -> PartResult {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input, _prepared: &()) -> PartResult {
+        Ok(part2(input))
+    }
+}
+
+impl Variants for Solver {
+    fn variants() -> Vec<Variant<Self>> {
+        vec![
+            Variant {
+                part: 1,
+                name: "backtracking",
+                solve: |input, _prepared| {
+                    Ok(calibration(input, |ueq| could_be_true(ueq, false)).into())
+                },
+            },
+            Variant {
+                part: 2,
+                name: "backtracking",
+                solve: |input, _prepared| {
+                    Ok(calibration(input, |ueq| could_be_true(ueq, true)).into())
+                },
+            },
+        ]
+    }
+}
+
+impl StreamSolution for Solver {
+    fn part1(source: &InputSource) -> PartResult {
+        stream_calibration(source, eq_could_be_true_part1)
+    }
+
+    fn part2(source: &InputSource) -> PartResult {
+        stream_calibration(source, eq_could_be_true_part2)
+    }
+}
+
+/// Sums the equations that could be true in batches of lines, so only one batch is held in
+/// memory at a time.
+fn stream_calibration(
+    source: &InputSource,
+    could_be_true: fn(&UnfinishedEquation) -> bool,
+) -> PartResult {
+    let mut lines = source.lines()?;
+    let mut total: Num = 0;
+    loop {
+        let batch: Input = lines
+            .by_ref()
+            .filter(|line| !matches!(line, Ok(line) if line.trim().is_empty()))
+            .take(STREAM_BATCH)
+            .map(|line| line.map(|line| parse_equation(&line)))
+            .collect::<io::Result<_>>()?;
+        if batch.is_empty() {
+            return Ok(total.into());
+        }
+        total += calibration(&batch, could_be_true);
+    }
+}
+
+fn get_input_from_str(input_str: String) -> Input {
+    input_str
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(parse_equation)
+        .collect()
+}
+
+fn parse_equation(line: &str) -> UnfinishedEquation {
+    let mut splitted = line.split(':');
+    let lhs = splitted.next().unwrap().parse::<Num>().unwrap();
+    let rhs_str = splitted.next().unwrap();
+    let rhs: Rhs = rhs_str
+        .split_whitespace()
+        .map(|s| s.parse::<Num>().unwrap())
+        .collect();
+
+    (lhs, rhs)
+}
+
+fn calibration(input: &Input, could_be_true: fn(&UnfinishedEquation) -> bool) -> Num {
+    input
+        .par_iter()
+        .filter(|ueq| could_be_true(ueq))
+        .map(|(lhs, _rhs)| lhs)
+        .sum()
+}
+
+fn part1(input: &Input) -> Answer {
+    calibration(input, eq_could_be_true_part1).into()
+}
+
+fn part2(input: &Input) -> Answer {
+    calibration(input, eq_could_be_true_part2).into()
+}
+
+/// One between each pair of numbers, so none for an equation of one number or of none.
+fn operator_count(rhs: &Rhs) -> usize {
+    rhs.len().saturating_sub(1)
+}
+
+fn eq_could_be_true_part1(ueq: &UnfinishedEquation) -> bool {
+    let (_lhs, rhs) = ueq;
+    possible_operators_part1(operator_count(rhs))
+        .par_iter()
+        .any(|ops| eq_is_true(ueq, ops))
+}
+
+fn eq_could_be_true_part2(ueq: &UnfinishedEquation) -> bool {
+    let (_lhs, rhs) = ueq;
+    possible_operators_part2(operator_count(rhs))
+        .par_iter()
+        .any(|ops| eq_is_true(ueq, ops))
+}
+
+/// A single number with no operators is true when it is the total.
+fn eq_is_true((lhs, rhs): &UnfinishedEquation, ops: &Operators) -> bool {
+    let Some(&first) = rhs.first() else {
+        return false;
+    };
+    let mut result = first;
+    // Every operator keeps a result from shrinking, except multiplying by zero.
+    let last_zero = rhs.iter().rposition(|&num| num == 0);
+
+    for (i, (op, &num)) in ops.iter().zip(&rhs[1..]).enumerate() {
+        match op.as_str() {
+            "+" => result = result.saturating_add(num),
+            "*" => result = result.saturating_mul(num),
+            "||" => result = concat_nums(result, num),
+            _ => panic!("received invalid op: {}", op),
+        };
+
+        if &result > lhs && last_zero.is_none_or(|zero| zero <= i + 1) {
+            return false;
+        }
+    }
+
+    &result == lhs
+}
+
+/// Works back from the total, undoing the last operator at each step and pruning the ones
+/// that cannot have given it, instead of trying every combination of operators.
+fn could_be_true((lhs, rhs): &UnfinishedEquation, concat: bool) -> bool {
+    fn undo(total: Num, rhs: &[Num], concat: bool) -> bool {
+        let (&last, rest) = rhs.split_last().unwrap();
+        if rest.is_empty() {
+            return total == last;
+        }
+        // Multiplying by zero gives zero whatever came before.
+        let product = match last {
+            0 => total == 0,
+            _ => total.is_multiple_of(last) && undo(total / last, rest, concat),
+        };
+        if product {
+            return true;
+        }
+        if total >= last && undo(total - last, rest, concat) {
+            return true;
+        }
+        let Some(shift) = (10 as Num).checked_pow(last.to_string().len() as u32) else {
+            return false;
+        };
+        concat && total >= last && total % shift == last && undo(total / shift, rest, concat)
+    }
+
+    !rhs.is_empty() && undo(*lhs, rhs, concat)
+}
+
+/// Saturates at `Num::MAX`, like the other operators.
+fn concat_nums(a: Num, b: Num) -> Num {
+    format!("{}{}", a, b).parse().unwrap_or(Num::MAX)
+}
+
+fn possible_operators_part1(len: usize) -> Vec<Operators> {
+    let valid_operators = vec!["+".into(), "*".into()];
+    possible_operators(len, valid_operators)
+}
+
+fn possible_operators_part2(len: usize) -> Vec<Operators> {
+    let valid_operators = vec!["+".into(), "*".into(), "||".into()];
+    possible_operators(len, valid_operators)
+}
+
+fn possible_operators(len: usize, valid_operators: Vec<Operator>) -> Vec<Operators> {
+    (0..len)
+        .map(|_| valid_operators.to_vec())
+        .multi_cartesian_product()
+        .collect()
+}
+
+fn format_expression((lhs, rhs): &UnfinishedEquation, ops: &Operators) -> String {
+    let mut expression = format!("{lhs} = {}", rhs[0]);
+    for (op, num) in ops.iter().zip(&rhs[1..]) {
+        expression += &format!(" {op} {num}");
+    }
+    expression
+}
+
+pub fn explain(input_str: &str) -> Trace {
+    let input = get_input_from_str(input_str.into());
+    let mut trace = Trace::default();
+
+    for (part, answer, possible_operators) in [
+        (
+            1,
+            part1(&input),
+            possible_operators_part1 as fn(usize) -> Vec<Operators>,
+        ),
+        (2, part2(&input), possible_operators_part2),
+    ] {
+        let part_trace = trace.part(part, answer);
+        for (i, ueq) in input.iter().enumerate() {
+            let solution = possible_operators(operator_count(&ueq.1))
+                .into_iter()
+                .find(|ops| eq_is_true(ueq, ops));
+            if let Some(ops) = solution {
+                part_trace.step(Some(i + 1), "solution", format_expression(ueq, &ops));
+            }
+        }
+    }
+
+    trace
+}
+
+/// `size` equations of two to eight numbers. About half can be made true with some operators,
+/// the others are one more than a true total.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        let rhs: Rhs = (0..rng.range(2, 9))
+            .map(|_| rng.range(1, 100) as Num)
+            .collect();
+        let mut lhs = rhs[0];
+        for &num in &rhs[1..] {
+            lhs = match rng.range(0, 3) {
+                0 => lhs + num,
+                1 => lhs * num,
+                _ => concat_nums(lhs, num),
+            };
+        }
+        if rng.chance(1, 2) {
+            lhs += 1;
+        }
+        let rhs: Vec<String> = rhs.iter().map(Num::to_string).collect();
+        input += &format!("{lhs}: {}\n", rhs.join(" "));
+    }
+    input
+}
+
+/// Each line is a total, a colon and at least one number.
+pub fn lint(input: &str) -> Vec<Violation> {
+    let mut violations = lint::non_empty(input);
+    violations.extend(lint::each_line(input, |number, line| {
+        let Some((lhs, rhs)) = line.split_once(':') else {
+            return vec![Violation::line(number, "expected `total: numbers`")];
+        };
+        let mut violations = vec![];
+        if lhs.trim().parse::<Num>().is_err() {
+            violations.push(Violation::line(
+                number,
+                format!("expected a total, found {:?}", lhs.trim()),
+            ));
+        }
+        match lint::numbers::<Num>(number, rhs, None) {
+            Ok(nums) if nums.is_empty() => violations.push(Violation::line(
+                number,
+                "expected at least one number after the colon",
+            )),
+            Ok(_) => {}
+            Err(e) => violations.extend(e),
+        }
+        violations
+    }));
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::PartFn;
+
+    const TEST_DATA_STR: &str = "190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20";
+
+    #[test]
+    fn test_part1() {
+        let data = get_input_from_str(TEST_DATA_STR.into());
+        let answer = part1(&data);
+        assert_eq!(answer, 3749)
+    }
+
+    #[test]
+    fn test_part2() {
+        let data = get_input_from_str(TEST_DATA_STR.into());
+        let answer = part2(&data);
+        assert_eq!(answer, 11387)
+    }
+
+    #[test]
+    fn test_stream() {
+        let source = InputSource::Text(TEST_DATA_STR.into());
+        assert_eq!(<Solver as StreamSolution>::part1(&source).unwrap(), 3749);
+        assert_eq!(<Solver as StreamSolution>::part2(&source).unwrap(), 11387);
+    }
+
+    #[test]
+    fn test_explain() {
+        let expected = "Part 1: 3749\n  \
+            line 1: solution: 190 = 10 * 19\n  \
+            line 2: solution: 3267 = 81 + 40 * 27\n  \
+            line 9: solution: 292 = 11 + 6 * 16 + 20\n\
+            Part 2: 11387\n  \
+            line 1: solution: 190 = 10 * 19\n  \
+            line 2: solution: 3267 = 81 + 40 * 27\n  \
+            line 4: solution: 156 = 15 || 6\n  \
+            line 5: solution: 7290 = 6 * 8 || 6 * 15\n  \
+            line 7: solution: 192 = 17 || 8 + 14\n  \
+            line 9: solution: 292 = 11 + 6 * 16 + 20\n";
+        assert_eq!(explain(TEST_DATA_STR).to_string(), expected);
+    }
+
+    #[test]
+    fn test_eq_could_be_true_part2() {
+        let mut ueq = (156, vec![15, 6]);
+        assert!(eq_could_be_true_part2(&ueq));
+
+        ueq = (7290, vec![6, 8, 6, 15]);
+        assert!(eq_could_be_true_part2(&ueq));
+    }
+
+    #[test]
+    fn test_backtracking() {
+        let input = get_input_from_str(generate(200, &mut Rng::new(3)));
+        for ueq in &input {
+            assert_eq!(could_be_true(ueq, false), eq_could_be_true_part1(ueq));
+            assert_eq!(could_be_true(ueq, true), eq_could_be_true_part2(ueq));
+        }
+        assert!(could_be_true(&(0, vec![5, 0]), false));
+
+        // The numbers before the last can add up to nothing, or to all of the total.
+        let input =
+            get_input_from_str("5: 0 5\n5: 5 0\n0: 0 0 0\n7: 0 7 0\n12: 0 12\n4: 0 5\n".into());
+        for ueq in &input {
+            assert_eq!(could_be_true(ueq, false), eq_could_be_true_part1(ueq));
+            assert_eq!(could_be_true(ueq, true), eq_could_be_true_part2(ueq));
+        }
+        assert!(could_be_true(&(5, vec![0, 5]), false));
+        assert!(could_be_true(&(12, vec![0, 12]), true));
+        assert!(!could_be_true(&(4, vec![0, 5]), true));
+    }
+
+    #[test]
+    fn test_variants_agree() {
+        // A later `* 0` brings a total that went over back down.
+        let input = Solver::parse(
+            "5: 4 2 0 5
+0: 9 9 0
+9: 0 9
+3: 7 0 3
+1: 99 99 99 0 1
+",
+        );
+        let main: [PartFn<Solver>; 2] = [<Solver as Solution>::part1, <Solver as Solution>::part2];
+        assert_eq!(main[0](&input, &()), Ok(18.into()));
+        for variant in Solver::variants() {
+            assert_eq!(
+                (variant.solve)(&input, &()),
+                main[variant.part as usize - 1](&input, &()),
+                "{} part {}",
+                variant.name,
+                variant.part
+            );
+        }
+    }
+
+    #[test]
+    fn test_degenerate() {
+        // One number is true on its own when it is the total, and no numbers never are.
+        let input = get_input_from_str("5: 5\n6: 5\n7:\n".into());
+        assert_eq!(part1(&input), 5);
+        assert_eq!(part2(&input), 5);
+        for ueq in &input {
+            assert_eq!(could_be_true(ueq, true), eq_could_be_true_part2(ueq));
+        }
+        assert_eq!(part1(&get_input_from_str("".into())), 0);
+        assert_eq!(part1(&get_input_from_str("\n".into())), 0);
+        assert_eq!(part2(&get_input_from_str("\n".into())), 0);
+        let source = InputSource::Text("\n5: 5\n\n".into());
+        assert_eq!(<Solver as StreamSolution>::part2(&source).unwrap(), 5);
+        assert_eq!(
+            explain("5: 5").to_string(),
+            "Part 1: 5\n  line 1: solution: 5 = 5\nPart 2: 5\n  line 1: solution: 5 = 5\n"
+        );
+    }
+
+    #[test]
+    fn test_eq_is_true() {
+        let lhs: Lhs = 44209555513;
+        let rhs: Rhs = vec![44, 209, 55, 49, 6, 13];
+        let ops: Operators = vec![
+            "||".to_string(),
+            "||".to_string(),
+            "||".to_string(),
+            "+".to_string(),
+            "||".to_string(),
+        ];
+        assert!(eq_is_true(&(lhs, rhs), &ops));
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = get_input_from_str(generate(100, &mut Rng::new(1)));
+        assert_eq!(input.len(), 100);
+        assert!(input.iter().any(eq_could_be_true_part2));
+    }
+
+    #[test]
+    fn test_lint() {
+        assert!(lint(TEST_DATA_STR).is_empty());
+        assert_eq!(
+            lint("190 10 19\n83:\nx: 1 2\n"),
+            vec![
+                Violation::line(1, "expected `total: numbers`"),
+                Violation::line(2, "expected at least one number after the colon"),
+                Violation::line(3, "expected a total, found \"x\""),
+            ]
+        );
+    }
+}
@@ -0,0 +1,267 @@
+use crate::{
+    advent::common::Point,
+    answer::Answer,
+    lint::{self, Violation},
+    rng::Rng,
+    solution::{PartResult, Solution},
+};
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
+
+type Cell = char;
+type Grid = Vec<Vec<Cell>>;
+type Positions = Vec<Point>;
+type Frequency = char;
+type Antennas = HashMap<Frequency, Positions>;
+type Input = (Grid, Antennas);
+type FrequencyAntinodes = HashSet<Point>;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = Input;
+    type Prepared = ();
+
+    fn parse(input_str: &str) -> Self::Input {
+        get_input_from_str(input_str.into())
+    }
+
+    fn prepare(_input: &Self::Input) {}
+
+    fn part1(input: &Self::Input, _prepared: &()) -> PartResult {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input, _prepared: &()) -> PartResult {
+        Ok(part2(input))
+    }
+}
+
+fn get_input_from_str(input_str: String) -> Input {
+    let grid: Grid = input_str
+        .lines()
+        .map(|line| line.chars().collect())
+        .collect();
+
+    let mut antennas = HashMap::new();
+    for (y, row) in grid.iter().enumerate() {
+        for (x, &ch) in row.iter().enumerate() {
+            if ch != '.' {
+                antennas
+                    .entry(ch)
+                    .or_insert_with(Vec::new)
+                    .push(Point::new(x as i64, y as i64));
+            }
+        }
+    }
+
+    (grid, antennas)
+}
+
+fn part1(input: &Input) -> Answer {
+    count_antinodes(input, false).into()
+}
+
+fn part2(input: &Input) -> Answer {
+    count_antinodes(input, true).into()
+}
+
+fn count_antinodes((grid, antennas): &Input, use_harmonics: bool) -> usize {
+    let height = grid.len();
+    let width = grid.first().map_or(0, Vec::len);
+
+    let mut antinodes: FrequencyAntinodes = HashSet::new();
+    for positions in antennas.values() {
+        let freq_antinodes = get_freq_antinodes(positions, height, width, use_harmonics);
+        antinodes.extend(freq_antinodes);
+    }
+
+    antinodes.len()
+}
+
+fn get_freq_antinodes(
+    anten_positions: &Positions,
+    height: usize,
+    width: usize,
+    use_harmonics: bool,
+) -> Positions {
+    let mut freq_antinodes = vec![];
+
+    for (ant1, ant2) in anten_positions.iter().tuple_combinations() {
+        let anti_positions = if use_harmonics {
+            get_anten_pair_antinodes_part2(ant1, ant2, height, width)
+        } else {
+            get_anten_pair_antinodes_part1(ant1, ant2, height, width)
+        };
+        freq_antinodes.extend(anti_positions);
+    }
+
+    freq_antinodes
+}
+
+fn get_anten_pair_antinodes_part1(
+    ant1: &Point,
+    ant2: &Point,
+    height: usize,
+    width: usize,
+) -> Positions {
+    let diff = *ant2 - *ant1;
+
+    [*ant1 - diff, *ant2 + diff]
+        .into_iter()
+        .filter(|antinode| antinode.in_bounds(width, height))
+        .collect()
+}
+
+fn get_anten_pair_antinodes_part2(
+    ant1: &Point,
+    ant2: &Point,
+    height: usize,
+    width: usize,
+) -> Positions {
+    let mut antinodes = vec![*ant1, *ant2];
+    let diff = *ant2 - *ant1;
+
+    let mut pos = *ant1 - diff;
+    while pos.in_bounds(width, height) {
+        antinodes.push(pos);
+        pos -= diff;
+    }
+
+    let mut pos = *ant2 + diff;
+    while pos.in_bounds(width, height) {
+        antinodes.push(pos);
+        pos += diff;
+    }
+
+    antinodes
+}
+
+/// A map of `size` rows of 50 cells, as wide as the puzzle input, with an antenna of a random
+/// frequency in about one cell in 12.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    const WIDTH: usize = 50;
+    let frequencies: Vec<char> = ('0'..='9').chain('a'..='z').chain('A'..='Z').collect();
+    let mut map = String::new();
+    for _ in 0..size {
+        for _ in 0..WIDTH {
+            map.push(match rng.chance(1, 12) {
+                true => *rng.choose(&frequencies),
+                false => '.',
+            });
+        }
+        map.push('\n');
+    }
+    map
+}
+
+/// The map is a rectangular grid of empty cells and antennas named by a letter or digit.
+pub fn lint(input: &str) -> Vec<Violation> {
+    lint::grid(
+        input,
+        |c| c == '.' || c.is_ascii_alphanumeric(),
+        "'.' or an antenna",
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT_STR: &str = "............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............";
+
+    const TEST_INPUT_STR2: &str = "T.........
+...T......
+.T........
+..........
+..........
+..........
+..........
+..........
+..........
+..........";
+
+    #[test]
+    fn test_generate() {
+        let input = generate(40, &mut Rng::new(1));
+        assert!(lint(&input).is_empty());
+        let input = get_input_from_str(input);
+        assert_eq!((input.0.len(), input.0[0].len()), (40, 50));
+        assert_ne!(part1(&input), 0);
+        assert_ne!(part2(&input), 0);
+    }
+
+    #[test]
+    fn test_part1() {
+        let input = get_input_from_str(TEST_INPUT_STR.into());
+        let answer = part1(&input);
+        assert_eq!(answer, 14)
+    }
+
+    #[test]
+    fn test_get_anten_pair_nodes() {
+        let (ant1, ant2) = (Point::new(4, 3), Point::new(5, 5));
+        let answer = get_anten_pair_antinodes_part1(&ant1, &ant2, 10, 10);
+        assert_eq!(answer, vec![Point::new(3, 1), Point::new(6, 7)]);
+
+        let answer = get_anten_pair_antinodes_part1(&ant2, &ant1, 10, 10);
+        assert_eq!(answer, vec![Point::new(6, 7), Point::new(3, 1)]);
+    }
+
+    #[test]
+    fn test_get_freq_nodes() {
+        let (_grid, antennas) = get_input_from_str(TEST_INPUT_STR.into());
+        let (height, width) = (12, 12);
+        let answer = get_freq_antinodes(antennas.get(&'A').unwrap(), height, width, false);
+        assert_eq!(answer.len(), 5);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = get_input_from_str(TEST_INPUT_STR.into());
+        let answer = part2(&input);
+        assert_eq!(answer, 34);
+
+        let input = get_input_from_str(TEST_INPUT_STR2.into());
+        let answer = part2(&input);
+        assert_eq!(answer, 9);
+    }
+
+    #[test]
+    fn test_degenerate() {
+        // A lone antenna has no pair to make antinodes, and two fill a row of two in part 2.
+        let cases = [
+            ("", 0, 0),
+            ("\n", 0, 0),
+            (".", 0, 0),
+            ("A", 0, 0),
+            ("AA", 0, 2),
+            ("A\nA", 0, 2),
+        ];
+        for (input, part1_answer, part2_answer) in cases {
+            let input_data = get_input_from_str(input.into());
+            assert_eq!(part1(&input_data), part1_answer, "{input:?}");
+            assert_eq!(part2(&input_data), part2_answer, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn test_lint() {
+        assert!(lint("..0\nA..\n").is_empty());
+        assert_eq!(
+            lint("..0\nA.#\n"),
+            vec![Violation::at(2, 3, "expected '.' or an antenna, found '#'")]
+        );
+    }
+}
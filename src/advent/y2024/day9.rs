@@ -0,0 +1,268 @@
+use crate::{
+    answer::Answer,
+    lint::{self, Violation},
+    rng::Rng,
+    solution::{PartResult, Solution},
+};
+use std::{iter, ops::Range};
+
+type DiskMap = Vec<usize>;
+type DataPart1 = Vec<Option<usize>>;
+type FileList = Vec<(Range<usize>, u64)>;
+type FreeList = Vec<Range<usize>>;
+
+pub struct Solver;
+
+impl Solution for Solver {
+    type Input = DiskMap;
+    type Prepared = ();
+
+    fn parse(input_str: &str) -> Self::Input {
+        parse_disk_map(input_str)
+    }
+
+    fn prepare(_disk_map: &Self::Input) {}
+
+    fn part1(disk_map: &Self::Input, _prepared: &()) -> PartResult {
+        Ok(part1(&unpack_blocks(disk_map)))
+    }
+
+    fn part2(disk_map: &Self::Input, _prepared: &()) -> PartResult {
+        Ok(part2(disk_map))
+    }
+}
+
+fn parse_disk_map(input_str: &str) -> DiskMap {
+    input_str
+        .trim()
+        .chars()
+        .map(|c| c.to_digit(10).unwrap() as usize)
+        .collect()
+}
+
+fn unpack_blocks(disk_map: &DiskMap) -> DataPart1 {
+    let mut unpacked = vec![];
+
+    for (i, &num_blocks) in disk_map.iter().enumerate() {
+        let id_num = if i % 2 == 0 { Some(i / 2) } else { None };
+        unpacked.extend(iter::repeat_n(id_num, num_blocks));
+    }
+
+    unpacked
+}
+
+const FRAME_WIDTH: usize = 64;
+
+struct Compaction {
+    disk: DataPart1,
+    head: usize,
+    tail: usize,
+}
+
+impl Compaction {
+    fn new(disk: DataPart1) -> Self {
+        let tail = disk.len().saturating_sub(1);
+        Compaction {
+            disk,
+            head: 0,
+            tail,
+        }
+    }
+
+    /// Moves the last file block into the first free block, returning false once compacted.
+    /// The scans stop where they meet, so a disk with no free or no file blocks is compacted.
+    fn step(&mut self) -> bool {
+        while self.head < self.tail && self.disk[self.head].is_some() {
+            self.head += 1;
+        }
+        while self.head < self.tail && self.disk[self.tail].is_none() {
+            self.tail -= 1;
+        }
+
+        if self.head >= self.tail {
+            return false;
+        }
+
+        self.disk.swap(self.head, self.tail);
+        true
+    }
+}
+
+fn compact_part1(input: &DataPart1) -> DataPart1 {
+    let mut compaction = Compaction::new(input.clone());
+    while compaction.step() {}
+    compaction.disk
+}
+
+fn render_disk(disk: &DataPart1) -> String {
+    let mut rendered = String::new();
+
+    for row in disk.chunks(FRAME_WIDTH) {
+        for block in row {
+            rendered.push(match block {
+                Some(id) => char::from_digit((id % 36) as u32, 36).unwrap(),
+                None => '.',
+            });
+        }
+        rendered.push('\n');
+    }
+
+    rendered
+}
+
+pub fn frames(input_str: &str) -> impl Iterator<Item = String> {
+    let mut compaction = Compaction::new(unpack_blocks(&parse_disk_map(input_str)));
+    let first = render_disk(&compaction.disk);
+
+    iter::once(first).chain(iter::from_fn(move || {
+        compaction.step().then(|| render_disk(&compaction.disk))
+    }))
+}
+
+fn get_checksum_part1(defragged: &DataPart1) -> usize {
+    defragged
+        .iter()
+        .enumerate()
+        .map(|(i, id)| i * id.unwrap_or(0))
+        .sum()
+}
+
+fn part1(data: &DataPart1) -> Answer {
+    let compacted = compact_part1(data);
+    get_checksum_part1(&compacted).into()
+}
+
+fn part2(disk_map: &DiskMap) -> Answer {
+    let mut file_list: FileList = vec![];
+    let mut free_list: FreeList = vec![];
+    let mut current_index = 0;
+
+    for (i, &space_size) in disk_map.iter().enumerate() {
+        let space_range = current_index..current_index + space_size;
+        current_index = space_range.end;
+
+        if i % 2 == 0 {
+            file_list.push((space_range, i as u64 / 2));
+        } else {
+            free_list.push(space_range);
+        };
+    }
+
+    for file in file_list.iter_mut().rev() {
+        if let Some((i, free)) = free_list
+            .iter_mut()
+            .enumerate()
+            .find(|(_i, f)| f.end <= file.0.start && f.len() >= file.0.len())
+        {
+            let free_start = free.start;
+            *free = free_start + file.0.len()..free.end;
+            *file = (free_start..free_start + file.0.len(), file.1);
+            if free.start == free.end {
+                free_list.remove(i);
+            }
+        }
+    }
+
+    file_list
+        .into_iter()
+        .map(|f| f.0.sum::<usize>() as u64 * f.1)
+        .sum::<u64>()
+        .into()
+}
+
+/// A disk map of `size` files, each one to nine blocks long with up to nine free blocks after.
+pub fn generate(size: usize, rng: &mut Rng) -> String {
+    let mut disk_map = String::new();
+    for i in 0..size {
+        disk_map += &rng.range(1, 10).to_string();
+        if i + 1 < size {
+            disk_map += &rng.range(0, 10).to_string();
+        }
+    }
+    disk_map.push('\n');
+    disk_map
+}
+
+/// The disk map is one line of digits.
+pub fn lint(input: &str) -> Vec<Violation> {
+    let mut violations = lint::non_empty(input);
+    let input = input.trim_end();
+    if let Some((number, _)) = lint::numbered_lines(input).nth(1) {
+        violations.push(Violation::line(number, "expected the disk map on one line"));
+    }
+    violations.extend(lint::chars(input, |c| c.is_ascii_digit(), "a digit"));
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{snapshot::assert_snapshot, viz};
+
+    const TEST_INPUT_STR: &str = "2333133121414131402";
+
+    #[test]
+    fn test_part1() {
+        let data = unpack_blocks(&parse_disk_map(TEST_INPUT_STR));
+        let answer = part1(&data);
+        assert_eq!(answer, 1928);
+    }
+
+    #[test]
+    fn test_frames() {
+        // Each frame is one line, so the whole compaction fits in one snapshot.
+        assert_snapshot("day9_frames", &frames(TEST_INPUT_STR).collect::<String>());
+        assert_eq!(frames("12345").count(), 6);
+    }
+
+    #[test]
+    fn test_asciicast() {
+        let mut out = vec![];
+        viz::write_asciicast("2024/day9", Box::new(frames("12345")), 4, &mut out).unwrap();
+        assert_snapshot("day9_12345.cast", &String::from_utf8(out).unwrap());
+    }
+
+    #[test]
+    fn test_part2() {
+        let answer = part2(&parse_disk_map(TEST_INPUT_STR));
+        assert_eq!(answer, 2858);
+    }
+
+    #[test]
+    fn test_degenerate() {
+        // Empty disks, a lone file and files with no free space between them stay where they are.
+        let cases = [
+            ("", 0, 0),
+            ("0", 0, 0),
+            ("1", 0, 0),
+            ("01", 0, 0),
+            ("2", 0, 0),
+            ("102", 3, 3),
+        ];
+        for (input, part1_answer, part2_answer) in cases {
+            let disk_map = parse_disk_map(input);
+            assert_eq!(part1(&unpack_blocks(&disk_map)), part1_answer, "{input:?}");
+            assert_eq!(part2(&disk_map), part2_answer, "{input:?}");
+            assert!(frames(input).count() >= 1);
+        }
+    }
+
+    #[test]
+    fn test_generate() {
+        let disk_map = parse_disk_map(&generate(40, &mut Rng::new(1)));
+        assert_eq!(disk_map.len(), 79);
+        assert!(disk_map.iter().step_by(2).all(|&blocks| blocks > 0));
+    }
+
+    #[test]
+    fn test_lint() {
+        assert!(lint("2333133121414131402\n").is_empty());
+        assert_eq!(
+            lint("23 3\n13\n"),
+            vec![
+                Violation::line(2, "expected the disk map on one line"),
+                Violation::at(1, 3, "expected a digit, found ' '"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,194 @@
+//! Advent of Code 2024.
+
+#[cfg(feature = "day1")]
+pub mod day1;
+#[cfg(feature = "day10")]
+pub mod day10;
+#[cfg(feature = "day2")]
+pub mod day2;
+#[cfg(feature = "day3")]
+pub mod day3;
+#[cfg(feature = "day4")]
+pub mod day4;
+#[cfg(feature = "day5")]
+pub mod day5;
+#[cfg(feature = "day6")]
+pub mod day6;
+#[cfg(feature = "day7")]
+pub mod day7;
+#[cfg(feature = "day8")]
+pub mod day8;
+#[cfg(feature = "day9")]
+pub mod day9;
+
+use crate::advent::Day;
+// Only the day entries below use these, so a build without days would not.
+#[cfg(any(
+    feature = "day1",
+    feature = "day2",
+    feature = "day3",
+    feature = "day4",
+    feature = "day5",
+    feature = "day6",
+    feature = "day7",
+    feature = "day8",
+    feature = "day9",
+    feature = "day10"
+))]
+use crate::{minimize::Shape, solution};
+
+pub const YEAR: u32 = 2024;
+
+/// Only the days enabled through cargo features are compiled in and listed here.
+pub const DAYS: &[Day] = &[
+    #[cfg(feature = "day1")]
+    Day {
+        year: YEAR,
+        name: "day1",
+        title: "Historian Hysteria",
+        run: solution::run::<day1::Solver>,
+        parse: solution::parse::<day1::Solver>,
+        stream: Some(solution::run_stream::<day1::Solver>),
+        frames: None,
+        explain: None,
+        compare: Some(solution::run_variants::<day1::Solver>),
+        lint: Some(day1::lint),
+        generate: Some(day1::generate),
+        shape: Shape::Lines,
+    },
+    #[cfg(feature = "day2")]
+    Day {
+        year: YEAR,
+        name: "day2",
+        title: "Red-Nosed Reports",
+        run: solution::run::<day2::Solver>,
+        parse: solution::parse::<day2::Solver>,
+        stream: Some(solution::run_stream::<day2::Solver>),
+        frames: None,
+        explain: Some(day2::explain),
+        compare: None,
+        lint: Some(day2::lint),
+        generate: Some(day2::generate),
+        shape: Shape::Lines,
+    },
+    #[cfg(feature = "day3")]
+    Day {
+        year: YEAR,
+        name: "day3",
+        title: "Mull It Over",
+        run: solution::run::<day3::Solver>,
+        parse: solution::parse::<day3::Solver>,
+        stream: Some(solution::run_stream::<day3::Solver>),
+        frames: None,
+        explain: Some(day3::explain),
+        compare: None,
+        lint: Some(day3::lint),
+        generate: Some(day3::generate),
+        shape: Shape::Text,
+    },
+    #[cfg(feature = "day4")]
+    Day {
+        year: YEAR,
+        name: "day4",
+        title: "Ceres Search",
+        run: solution::run::<day4::Solver>,
+        parse: solution::parse::<day4::Solver>,
+        stream: None,
+        frames: None,
+        explain: None,
+        compare: None,
+        lint: Some(day4::lint),
+        generate: Some(day4::generate),
+        shape: Shape::Grid,
+    },
+    #[cfg(feature = "day5")]
+    Day {
+        year: YEAR,
+        name: "day5",
+        title: "Print Queue",
+        run: solution::run::<day5::Solver>,
+        parse: solution::parse::<day5::Solver>,
+        stream: None,
+        frames: None,
+        explain: Some(day5::explain),
+        compare: None,
+        lint: Some(day5::lint),
+        generate: Some(day5::generate),
+        shape: Shape::Sections,
+    },
+    #[cfg(feature = "day6")]
+    Day {
+        year: YEAR,
+        name: "day6",
+        title: "Guard Gallivant",
+        run: solution::run::<day6::Solver>,
+        parse: solution::parse::<day6::Solver>,
+        stream: None,
+        frames: Some(|input| Box::new(day6::frames(input))),
+        explain: None,
+        compare: None,
+        lint: Some(day6::lint),
+        generate: Some(day6::generate),
+        shape: Shape::Grid,
+    },
+    #[cfg(feature = "day7")]
+    Day {
+        year: YEAR,
+        name: "day7",
+        title: "Bridge Repair",
+        run: solution::run::<day7::Solver>,
+        parse: solution::parse::<day7::Solver>,
+        stream: Some(solution::run_stream::<day7::Solver>),
+        frames: None,
+        explain: Some(day7::explain),
+        compare: Some(solution::run_variants::<day7::Solver>),
+        lint: Some(day7::lint),
+        generate: Some(day7::generate),
+        shape: Shape::Lines,
+    },
+    #[cfg(feature = "day8")]
+    Day {
+        year: YEAR,
+        name: "day8",
+        title: "Resonant Collinearity",
+        run: solution::run::<day8::Solver>,
+        parse: solution::parse::<day8::Solver>,
+        stream: None,
+        frames: None,
+        explain: None,
+        compare: None,
+        lint: Some(day8::lint),
+        generate: Some(day8::generate),
+        shape: Shape::Grid,
+    },
+    #[cfg(feature = "day9")]
+    Day {
+        year: YEAR,
+        name: "day9",
+        title: "Disk Fragmenter",
+        run: solution::run::<day9::Solver>,
+        parse: solution::parse::<day9::Solver>,
+        stream: None,
+        frames: Some(|input| Box::new(day9::frames(input))),
+        explain: None,
+        compare: None,
+        lint: Some(day9::lint),
+        generate: Some(day9::generate),
+        shape: Shape::Text,
+    },
+    #[cfg(feature = "day10")]
+    Day {
+        year: YEAR,
+        name: "day10",
+        title: "Hoof It",
+        run: solution::run::<day10::Solver>,
+        parse: solution::parse::<day10::Solver>,
+        stream: None,
+        frames: None,
+        explain: None,
+        compare: None,
+        lint: Some(day10::lint),
+        generate: Some(day10::generate),
+        shape: Shape::Grid,
+    },
+];
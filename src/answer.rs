@@ -0,0 +1,165 @@
+use crate::json;
+use std::{convert::Infallible, fmt, str::FromStr};
+
+/// A puzzle answer. Integers of every width are stored as `i128` so that answers compare
+/// equal however they were computed.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i128),
+    Text(String),
+    Lines(Vec<String>),
+}
+
+impl Answer {
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Answer::Lines(_))
+    }
+
+    pub fn to_json(&self) -> String {
+        match self {
+            Answer::Int(n) => n.to_string(),
+            Answer::Text(s) => json::quote(s),
+            Answer::Lines(lines) => {
+                let lines: Vec<String> = lines.iter().map(|line| json::quote(line)).collect();
+                format!("[{}]", lines.join(", "))
+            }
+        }
+    }
+
+    /// A line naming the kind of answer, `int`, `text` or `lines`, then the answer. Unlike the
+    /// `Display` form it reads back as the same kind, so text such as `007` stays text when it
+    /// is passed between processes or from a plugin.
+    pub fn to_tagged(&self) -> String {
+        let kind = match self {
+            Answer::Int(_) => "int",
+            Answer::Text(_) => "text",
+            Answer::Lines(_) => "lines",
+        };
+        format!("{kind}\n{self}")
+    }
+
+    /// Reads back [`Answer::to_tagged`], or `None` if the kind is unknown or an `int` is not one.
+    pub fn from_tagged(s: &str) -> Option<Answer> {
+        let (kind, answer) = s.split_once('\n').unwrap_or((s, ""));
+        match kind {
+            "int" => answer.parse().ok().map(Answer::Int),
+            "text" => Some(Answer::Text(answer.into())),
+            "lines" => Some(Answer::Lines(answer.lines().map(String::from).collect())),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! impl_int_answer {
+    ($($int:ty),*) => {$(
+        impl From<$int> for Answer {
+            fn from(n: $int) -> Self {
+                Answer::Int(n as i128)
+            }
+        }
+
+        impl PartialEq<$int> for Answer {
+            fn eq(&self, other: &$int) -> bool {
+                matches!(self, Answer::Int(n) if *n == *other as i128)
+            }
+        }
+    )*};
+}
+
+impl_int_answer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+/// Text is kept as text even if it looks like a number, so that `007` stays `007`.
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        match s.contains('\n') {
+            true => Answer::Lines(s.lines().map(String::from).collect()),
+            false => Answer::Text(s),
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::from(s.to_string())
+    }
+}
+
+/// Reads back an answer from its `Display` form, so text from plugins or bare numbers in answer
+/// files compare equal to the same answer computed here.
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.parse::<i128>() {
+            Ok(n) => Answer::Int(n),
+            Err(_) => Answer::from(s),
+        })
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{n}"),
+            Answer::Text(s) => write!(f, "{s}"),
+            Answer::Lines(lines) => write!(f, "{}", lines.join("\n")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_int_widths_compare_equal() {
+        assert_eq!(Answer::from(41usize), Answer::from(41i32));
+        assert_eq!(Answer::from(u64::MAX), u64::MAX);
+        assert_eq!(Answer::from(-3i64), -3);
+        assert_ne!(Answer::from(41u64), Answer::from("forty-one"));
+    }
+
+    #[test]
+    fn test_text_stays_text() {
+        assert_eq!(Answer::from("007"), Answer::Text("007".into()));
+        assert_eq!(Answer::from("007").to_string(), "007");
+        assert_ne!(Answer::from("41"), Answer::from(41));
+        assert_eq!("41".parse::<Answer>().unwrap(), Answer::from(41));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for answer in [
+            Answer::from(-12),
+            Answer::from("a,b,c"),
+            Answer::Lines(vec!["#..#".into(), ".##.".into()]),
+        ] {
+            assert_eq!(answer.to_string().parse::<Answer>().unwrap(), answer);
+        }
+    }
+
+    #[test]
+    fn test_tagged_round_trip() {
+        for answer in [
+            Answer::from(-12),
+            Answer::from("007"),
+            Answer::from(""),
+            Answer::Lines(vec!["#..#".into(), ".##.".into()]),
+        ] {
+            assert_eq!(Answer::from_tagged(&answer.to_tagged()), Some(answer));
+        }
+        assert_eq!(Answer::from("007").to_tagged(), "text\n007");
+        assert_eq!(Answer::from_tagged("int\nx"), None);
+        assert_eq!(Answer::from_tagged("7"), None);
+    }
+
+    #[test]
+    fn test_to_json() {
+        assert_eq!(Answer::from(7u8).to_json(), "7");
+        assert_eq!(Answer::from("x\"y").to_json(), "\"x\\\"y\"");
+        assert_eq!(
+            Answer::Lines(vec!["ab".into(), "cd".into()]).to_json(),
+            "[\"ab\", \"cd\"]"
+        );
+    }
+}
@@ -0,0 +1,168 @@
+use crate::{solution::DayRun, toml};
+use std::{fs, io, path::Path, time::Duration};
+
+/// Where `bench` keeps the medians from its latest run of each day by default, as
+/// `YYYY.dayN.partM = seconds`.
+pub const BENCH_FILE: &str = "target/bench.toml";
+pub const DEFAULT_RUNS: usize = 10;
+
+/// The latest median time of each part that has been benchmarked, in the order first benched.
+#[derive(Debug, Default, PartialEq)]
+pub struct BenchResults {
+    medians: Vec<(u32, String, u32, Duration)>,
+}
+
+impl BenchResults {
+    /// Reads saved results. A missing file means nothing has been benchmarked yet.
+    pub fn load(path: &Path) -> Result<BenchResults, String> {
+        match fs::read_to_string(path) {
+            Ok(results_str) => {
+                BenchResults::from_toml(&results_str).map_err(|e| format!("{}:{e}", path.display()))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(BenchResults::default()),
+            Err(e) => Err(format!("Could not read {}: {e}", path.display())),
+        }
+    }
+
+    fn from_toml(results_str: &str) -> Result<BenchResults, String> {
+        let mut results = BenchResults::default();
+
+        for entry in toml::entries(results_str)? {
+            let parsed = entry.key.split_once('.').and_then(|(year, key)| {
+                let (day, part) = key.split_once(".part")?;
+                let seconds = entry.value.parse().ok()?;
+                Some((
+                    year.parse().ok()?,
+                    day,
+                    part.parse().ok()?,
+                    Duration::try_from_secs_f64(seconds).ok()?,
+                ))
+            });
+            let Some((year, day, part, median)) = parsed else {
+                return Err(format!(
+                    "{}: expected `YYYY.dayN.partM = seconds`, got {} = {}",
+                    entry.line, entry.key, entry.value
+                ));
+            };
+            results.set(year, day, part, median);
+        }
+
+        Ok(results)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Could not create {}: {e}", parent.display()))?;
+        }
+        fs::write(path, self.to_toml())
+            .map_err(|e| format!("Could not write {}: {e}", path.display()))
+    }
+
+    fn to_toml(&self) -> String {
+        let mut results_str = String::from("# Median seconds per part from the latest bench\n");
+        for (year, day, part, median) in &self.medians {
+            results_str.push_str(&format!(
+                "{year}.{day}.part{part} = {}\n",
+                median.as_secs_f64()
+            ));
+        }
+        results_str
+    }
+
+    pub fn set(&mut self, year: u32, day: &str, part: u32, median: Duration) {
+        match self
+            .medians
+            .iter_mut()
+            .find(|(y, d, p, _)| *y == year && d == day && *p == part)
+        {
+            Some((_, _, _, old_median)) => *old_median = median,
+            None => self.medians.push((year, day.to_string(), part, median)),
+        }
+    }
+
+    pub fn get(&self, year: u32, day: &str, part: u32) -> Option<Duration> {
+        self.medians
+            .iter()
+            .find(|(y, d, p, _)| *y == year && d == day && *p == part)
+            .map(|(_, _, _, median)| *median)
+    }
+}
+
+/// Runs a day `runs` times and returns the median time of each part.
+pub fn bench(run: impl Fn() -> DayRun, runs: usize) -> Vec<(u32, Duration)> {
+    let mut times: Vec<(u32, Vec<Duration>)> = vec![];
+
+    for _ in 0..runs {
+        for part_run in run().parts {
+            match times.iter_mut().find(|(part, _)| *part == part_run.part) {
+                Some((_, part_times)) => part_times.push(part_run.elapsed),
+                None => times.push((part_run.part, vec![part_run.elapsed])),
+            }
+        }
+    }
+
+    times
+        .into_iter()
+        .map(|(part, mut part_times)| (part, median(&mut part_times)))
+        .collect()
+}
+
+pub fn median(durations: &mut [Duration]) -> Duration {
+    durations.sort();
+    let mid = durations.len() / 2;
+    match durations.len() % 2 {
+        0 => (durations[mid - 1] + durations[mid]) / 2,
+        _ => durations[mid],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::PartRun;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_bench() {
+        let calls = Cell::new(0);
+        let run = || {
+            calls.set(calls.get() + 1);
+            let elapsed = Duration::from_millis(calls.get() * 10);
+            DayRun {
+                phases: vec![],
+                parts: vec![PartRun {
+                    part: 2,
+                    answer: Ok(0.into()),
+                    elapsed,
+                }],
+            }
+        };
+
+        assert_eq!(bench(run, 3), vec![(2, Duration::from_millis(20))]);
+        assert_eq!(
+            median(&mut [4, 1, 3, 2].map(Duration::from_millis)),
+            Duration::from_micros(2500)
+        );
+    }
+
+    #[test]
+    fn test_results_round_trip() {
+        let mut results = BenchResults::default();
+        results.set(2024, "day6", 1, Duration::from_millis(3));
+        results.set(2024, "day1", 2, Duration::from_micros(250));
+        results.set(2024, "day6", 1, Duration::from_millis(2));
+        assert_eq!(results.get(2024, "day6", 1), Some(Duration::from_millis(2)));
+        assert_eq!(results.get(2024, "day6", 2), None);
+        assert_eq!(results.get(2023, "day6", 1), None);
+
+        assert_eq!(
+            results.to_toml(),
+            "# Median seconds per part from the latest bench\n2024.day6.part1 = 0.002\n2024.day1.part2 = 0.00025\n"
+        );
+        assert_eq!(
+            BenchResults::from_toml(&results.to_toml()).unwrap(),
+            results
+        );
+    }
+}
@@ -0,0 +1,176 @@
+//! Running every variant of a day's parts on the same input, checking that they agree and
+//! comparing how long they take.
+
+use crate::{
+    bench,
+    solution::{self, PartResult, VariantRun, MAIN_VARIANT},
+};
+use std::{fmt::Write, time::Duration};
+
+pub const DEFAULT_RUNS: usize = 3;
+
+/// Each variant's name, answer and times, in the order first run.
+type VariantTimes = Vec<(&'static str, PartResult, Vec<Duration>)>;
+
+pub struct VariantTime {
+    pub name: &'static str,
+    pub answer: PartResult,
+    pub median: Duration,
+}
+
+/// The variants of one part, with the main one first.
+pub struct PartComparison {
+    pub part: u32,
+    pub variants: Vec<VariantTime>,
+}
+
+impl PartComparison {
+    /// The variants whose answer is not the main one's, including failing where it did not
+    /// or failing differently.
+    pub fn disagreements(&self) -> Vec<&VariantTime> {
+        let main = &self.variants[0].answer;
+        self.variants
+            .iter()
+            .filter(|variant| variant.answer != *main)
+            .collect()
+    }
+
+    /// How many times longer each variant takes than the main one, or `None` if the main one
+    /// took no measurable time.
+    pub fn relative(&self, variant: &VariantTime) -> Option<f64> {
+        let main = self.variants[0].median;
+        (!main.is_zero()).then(|| variant.median.as_secs_f64() / main.as_secs_f64())
+    }
+}
+
+/// Runs the variants `runs` times, taking each one's answer from its first run and its
+/// median time.
+pub fn compare(run: impl Fn() -> Vec<VariantRun>, runs: usize) -> Vec<PartComparison> {
+    let mut parts: Vec<(u32, VariantTimes)> = vec![];
+
+    for _ in 0..runs {
+        for variant_run in run() {
+            let i = match parts.iter().position(|(part, _)| *part == variant_run.part) {
+                Some(i) => i,
+                None => {
+                    parts.push((variant_run.part, vec![]));
+                    parts.len() - 1
+                }
+            };
+            let part = &mut parts[i].1;
+            match part.iter_mut().find(|(name, ..)| *name == variant_run.name) {
+                Some((_, _, times)) => times.push(variant_run.elapsed),
+                None => part.push((
+                    variant_run.name,
+                    variant_run.answer,
+                    vec![variant_run.elapsed],
+                )),
+            }
+        }
+    }
+
+    parts
+        .into_iter()
+        .map(|(part, mut variants)| {
+            variants.sort_by_key(|(name, ..)| *name != MAIN_VARIANT);
+            PartComparison {
+                part,
+                variants: variants
+                    .into_iter()
+                    .map(|(name, answer, mut times)| VariantTime {
+                        name,
+                        answer,
+                        median: bench::median(&mut times),
+                    })
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+/// With two significant digits when under 1, so that much faster variants do not show as 0.
+fn format_relative(relative: Option<f64>) -> String {
+    relative.map_or("-".into(), |relative| {
+        let decimals = match relative {
+            r if r > 0.0 && r < 1.0 => (1.0 - r.log10().floor()) as usize,
+            _ => 2,
+        };
+        format!("{relative:.decimals$}x")
+    })
+}
+
+/// One row per variant with its answer, median time and time relative to the main variant,
+/// marking the answers that disagree.
+pub fn table(comparisons: &[PartComparison]) -> String {
+    let mut table = String::new();
+    for comparison in comparisons {
+        writeln!(table, "Part {}", comparison.part).unwrap();
+        let disagreements = comparison.disagreements();
+        for variant in &comparison.variants {
+            let mark = match disagreements.iter().any(|d| d.name == variant.name) {
+                true => "  (disagrees)",
+                false => "",
+            };
+            writeln!(
+                table,
+                "  {:<16}{:>20}{:>14}{:>10}{mark}",
+                variant.name,
+                solution::format_result(&variant.answer),
+                format!("{:.2?}", variant.median),
+                format_relative(comparison.relative(variant))
+            )
+            .unwrap();
+        }
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variant_run(part: u32, name: &'static str, answer: i32, micros: u64) -> VariantRun {
+        VariantRun {
+            part,
+            name,
+            answer: Ok(answer.into()),
+            elapsed: Duration::from_micros(micros),
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        let comparisons = compare(
+            || {
+                vec![
+                    variant_run(1, "fast", 7, 50),
+                    variant_run(1, MAIN_VARIANT, 7, 200),
+                    variant_run(2, MAIN_VARIANT, 3, 100),
+                    variant_run(2, "wrong", 4, 100),
+                ]
+            },
+            3,
+        );
+
+        let names: Vec<&str> = comparisons[0].variants.iter().map(|v| v.name).collect();
+        assert_eq!(names, vec![MAIN_VARIANT, "fast"]);
+        assert!(comparisons[0].disagreements().is_empty());
+        let fast = &comparisons[0].variants[1];
+        assert_eq!(comparisons[0].relative(fast), Some(0.25));
+
+        let disagreements = comparisons[1].disagreements();
+        assert_eq!(disagreements.len(), 1);
+        assert_eq!(disagreements[0].name, "wrong");
+
+        let table = table(&comparisons);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "Part 1");
+        assert_eq!(
+            lines[2],
+            "  fast                               7       50.00µs     0.25x"
+        );
+        assert!(lines[5].ends_with("1.00x  (disagrees)"));
+        assert_eq!(format_relative(Some(0.000183)), "0.00018x");
+        assert_eq!(format_relative(Some(12.5)), "12.50x");
+    }
+}
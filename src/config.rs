@@ -0,0 +1,364 @@
+use crate::{bench, fuzz, json, toml};
+use std::{env, fmt, fs, path::PathBuf, time::Duration};
+
+pub const CONFIG_ENV_VAR: &str = "AOC_CONFIG";
+const DEFAULT_CONFIG_PATH: &str = "aoc.toml";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    pub source: Option<PathBuf>,
+    pub input_dir: PathBuf,
+    pub plugin_dir: PathBuf,
+    pub threads: Option<usize>,
+    pub format: Format,
+    pub speed: u32,
+    /// Runs each part in a child process, see [`crate::isolate`].
+    pub isolate: bool,
+    /// The address space an isolated part may use, in MiB.
+    pub memory_limit: Option<u64>,
+    /// The CPU time an isolated part may use, in seconds.
+    pub cpu_limit: Option<u64>,
+    /// The wall-clock time an isolated part may take, in seconds.
+    pub wall_limit: Option<u64>,
+    /// How long `fuzz` and `minimize` give a day to parse and solve one input.
+    pub time_limit: Duration,
+    /// Where `bench` keeps the latest medians, for `report`.
+    pub bench_file: PathBuf,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            source: None,
+            input_dir: PathBuf::from("data"),
+            plugin_dir: PathBuf::from("plugins"),
+            threads: None,
+            format: Format::Text,
+            speed: 30,
+            isolate: false,
+            memory_limit: None,
+            cpu_limit: None,
+            wall_limit: None,
+            time_limit: fuzz::DEFAULT_TIME_LIMIT,
+            bench_file: PathBuf::from(bench::BENCH_FILE),
+        }
+    }
+}
+
+impl Config {
+    /// Reads the file named by `$AOC_CONFIG`, or `aoc.toml` if it exists, over the defaults.
+    pub fn load() -> Result<Config, String> {
+        let path = match env::var_os(CONFIG_ENV_VAR) {
+            Some(path) => PathBuf::from(path),
+            None if fs::metadata(DEFAULT_CONFIG_PATH).is_ok() => PathBuf::from(DEFAULT_CONFIG_PATH),
+            None => return Ok(Config::default()),
+        };
+
+        let config_str = fs::read_to_string(&path)
+            .map_err(|e| format!("Could not read {}: {e}", path.display()))?;
+        let mut config =
+            Config::from_toml(&config_str).map_err(|e| format!("{}:{e}", path.display()))?;
+        config.source = Some(path);
+        Ok(config)
+    }
+
+    /// Parses the flat `key = value` subset of TOML that the config file uses.
+    fn from_toml(config_str: &str) -> Result<Config, String> {
+        let mut config = Config::default();
+
+        for entry in toml::entries(config_str)? {
+            config
+                .set(&entry.key, &entry.value)
+                .map_err(|e| format!("{}: {e}", entry.line))?;
+        }
+
+        Ok(config)
+    }
+
+    /// Applies `--<key> <value>` overrides, returning the arguments that are not config flags.
+    /// Switches such as `--isolate` turn their setting on, or set it to a `true` or `false` that
+    /// follows them, so that a flag can turn off what the file turned on.
+    pub fn apply_flags(&mut self, args: &[String]) -> Result<Vec<String>, String> {
+        let mut rest = vec![];
+        let mut args = args.iter().peekable();
+
+        while let Some(arg) = args.next() {
+            let key = arg.strip_prefix("--").map(|flag| flag.replace('-', "_"));
+            match key {
+                Some(key) if Config::is_switch(&key) => {
+                    let value = args
+                        .next_if(|value| *value == "true" || *value == "false")
+                        .map_or("true", String::as_str);
+                    self.set(&key, value)?;
+                }
+                Some(key) if Config::is_key(&key) => {
+                    let value = args
+                        .next()
+                        .ok_or_else(|| format!("Missing value for {arg}"))?;
+                    self.set(&key, value)?;
+                }
+                _ => rest.push(arg.clone()),
+            }
+        }
+
+        Ok(rest)
+    }
+
+    fn is_key(key: &str) -> bool {
+        matches!(
+            key,
+            "input_dir"
+                | "plugin_dir"
+                | "threads"
+                | "format"
+                | "speed"
+                | "memory_limit"
+                | "cpu_limit"
+                | "wall_limit"
+                | "time_limit"
+                | "bench_file"
+        )
+    }
+
+    fn is_switch(key: &str) -> bool {
+        key == "isolate"
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "input_dir" => self.input_dir = PathBuf::from(value),
+            "plugin_dir" => self.plugin_dir = PathBuf::from(value),
+            "threads" => match value.parse() {
+                Ok(threads) if threads > 0 => self.threads = Some(threads),
+                _ => return Err(format!("Invalid threads: {value}")),
+            },
+            "format" => {
+                self.format = match value {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    _ => return Err(format!("Invalid format: {value}")),
+                }
+            }
+            "speed" => match value.parse() {
+                Ok(speed) if speed > 0 => self.speed = speed,
+                _ => return Err(format!("Invalid speed: {value}")),
+            },
+            "isolate" => match value.parse() {
+                Ok(isolate) => self.isolate = isolate,
+                Err(_) => return Err(format!("Invalid isolate: {value}")),
+            },
+            "memory_limit" => match value.parse() {
+                Ok(memory_limit) if memory_limit > 0 => self.memory_limit = Some(memory_limit),
+                _ => return Err(format!("Invalid memory_limit: {value}")),
+            },
+            "cpu_limit" => match value.parse() {
+                Ok(cpu_limit) if cpu_limit > 0 => self.cpu_limit = Some(cpu_limit),
+                _ => return Err(format!("Invalid cpu_limit: {value}")),
+            },
+            "wall_limit" => match value.parse() {
+                Ok(wall_limit) if wall_limit > 0 => self.wall_limit = Some(wall_limit),
+                _ => return Err(format!("Invalid wall_limit: {value}")),
+            },
+            "time_limit" => match value.parse().map(Duration::try_from_secs_f64) {
+                Ok(Ok(time_limit)) if !time_limit.is_zero() => self.time_limit = time_limit,
+                _ => return Err(format!("Invalid time_limit: {value}")),
+            },
+            "bench_file" => self.bench_file = PathBuf::from(value),
+            _ => return Err(format!("Unknown setting: {key}")),
+        }
+
+        Ok(())
+    }
+
+    pub fn to_json(&self) -> String {
+        let source = self.source.as_ref().map_or("null".into(), |source| {
+            json::quote(&source.display().to_string())
+        });
+        let or_null = |n: Option<u64>| n.map_or("null".into(), |n| n.to_string());
+        let threads = or_null(self.threads.map(|threads| threads as u64));
+
+        format!(
+            "{{\"source\": {source}, \"input_dir\": {}, \"plugin_dir\": {}, \"threads\": {threads}, \"format\": {}, \"speed\": {}, \"isolate\": {}, \"memory_limit\": {}, \"cpu_limit\": {}, \"wall_limit\": {}, \"time_limit\": {}, \"bench_file\": {}}}",
+            json::quote(&self.input_dir.display().to_string()),
+            json::quote(&self.plugin_dir.display().to_string()),
+            json::quote(&self.format.to_string()),
+            self.speed,
+            self.isolate,
+            or_null(self.memory_limit),
+            or_null(self.cpu_limit),
+            or_null(self.wall_limit),
+            self.time_limit.as_secs_f64(),
+            json::quote(&self.bench_file.display().to_string())
+        )
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Format::Text => write!(f, "text"),
+            Format::Json => write!(f, "json"),
+        }
+    }
+}
+
+impl fmt::Display for Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.source {
+            Some(source) => writeln!(f, "# from {}", source.display())?,
+            None => writeln!(f, "# defaults")?,
+        }
+        writeln!(
+            f,
+            "input_dir = {}",
+            json::quote(&self.input_dir.display().to_string())
+        )?;
+        writeln!(
+            f,
+            "plugin_dir = {}",
+            json::quote(&self.plugin_dir.display().to_string())
+        )?;
+        match self.threads {
+            Some(threads) => writeln!(f, "threads = {threads}")?,
+            None => writeln!(f, "# threads = <all cores>")?,
+        }
+        writeln!(f, "format = \"{}\"", self.format)?;
+        writeln!(f, "speed = {}", self.speed)?;
+        writeln!(f, "isolate = {}", self.isolate)?;
+        match self.memory_limit {
+            Some(memory_limit) => writeln!(f, "memory_limit = {memory_limit}")?,
+            None => writeln!(f, "# memory_limit = <none>")?,
+        }
+        match self.cpu_limit {
+            Some(cpu_limit) => writeln!(f, "cpu_limit = {cpu_limit}")?,
+            None => writeln!(f, "# cpu_limit = <none>")?,
+        }
+        match self.wall_limit {
+            Some(wall_limit) => writeln!(f, "wall_limit = {wall_limit}")?,
+            None => writeln!(f, "# wall_limit = <none>")?,
+        }
+        writeln!(f, "time_limit = {}", self.time_limit.as_secs_f64())?;
+        writeln!(
+            f,
+            "bench_file = {}",
+            json::quote(&self.bench_file.display().to_string())
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_toml() {
+        let config_str = "# runner defaults\n\
+                          input_dir = \"inputs/#2024\" # private inputs\n\
+                          \n\
+                          threads = 4\n\
+                          format = \"json\"\n";
+        let config = Config::from_toml(config_str).unwrap();
+        assert_eq!(config.input_dir, PathBuf::from("inputs/#2024"));
+        assert_eq!(config.threads, Some(4));
+        assert_eq!(config.format, Format::Json);
+        assert_eq!(config.speed, 30);
+    }
+
+    #[test]
+    fn test_from_toml_errors() {
+        assert_eq!(
+            Config::from_toml("speed = 10\ncolour = \"red\"").unwrap_err(),
+            "2: Unknown setting: colour"
+        );
+        assert_eq!(
+            Config::from_toml("threads = 0").unwrap_err(),
+            "1: Invalid threads: 0"
+        );
+        assert_eq!(
+            Config::from_toml("isolate = 1").unwrap_err(),
+            "1: Invalid isolate: 1"
+        );
+        assert_eq!(
+            Config::from_toml("format").unwrap_err(),
+            "1: expected `key = value`"
+        );
+    }
+
+    #[test]
+    fn test_apply_flags() {
+        let mut config = Config::from_toml("speed = 10\nformat = \"json\"").unwrap();
+        let args: Vec<String> = [
+            "day6",
+            "--speed",
+            "60",
+            "--cast",
+            "out.cast",
+            "--input-dir",
+            "x",
+            "--isolate",
+            "--memory-limit",
+            "512",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+        let rest = config.apply_flags(&args).unwrap();
+        assert_eq!(rest, vec!["day6", "--cast", "out.cast"]);
+        assert_eq!(config.speed, 60);
+        assert_eq!(config.format, Format::Json);
+        assert_eq!(config.input_dir, PathBuf::from("x"));
+        assert!(config.isolate);
+        assert_eq!(config.memory_limit, Some(512));
+
+        // A switch takes an optional value, so it can turn off what the file turned on.
+        let mut config = Config::from_toml("isolate = true").unwrap();
+        let args: Vec<String> = ["--isolate", "false", "day6"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(config.apply_flags(&args).unwrap(), vec!["day6"]);
+        assert!(!config.isolate);
+        let args: Vec<String> = ["--isolate", "true", "day6"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(config.apply_flags(&args).unwrap(), vec!["day6"]);
+        assert!(config.isolate);
+    }
+
+    #[test]
+    fn test_precedence() {
+        let defaults = Config::default();
+        assert_eq!(defaults.time_limit, fuzz::DEFAULT_TIME_LIMIT);
+        assert_eq!(defaults.bench_file, PathBuf::from(bench::BENCH_FILE));
+
+        let mut config =
+            Config::from_toml("time_limit = 2.5\nbench_file = \"cache/bench.toml\"").unwrap();
+        assert_eq!(config.time_limit, Duration::from_millis(2500));
+        assert_eq!(config.bench_file, PathBuf::from("cache/bench.toml"));
+
+        let args: Vec<String> = ["fuzz", "--time-limit", "1"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(config.apply_flags(&args).unwrap(), vec!["fuzz"]);
+        assert_eq!(config.time_limit, Duration::from_secs(1));
+        assert_eq!(config.bench_file, PathBuf::from("cache/bench.toml"));
+
+        assert!(config.to_string().contains("time_limit = 1\n"));
+        assert!(config
+            .to_json()
+            .ends_with(", \"time_limit\": 1, \"bench_file\": \"cache/bench.toml\"}"));
+        assert_eq!(
+            Config::from_toml("time_limit = 0").unwrap_err(),
+            "1: Invalid time_limit: 0"
+        );
+    }
+}
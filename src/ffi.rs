@@ -0,0 +1,193 @@
+//! A C ABI for calling the solvers from other languages, declared in `ffi/aoc.h`. Build the
+//! shared library, `target/release/libadvent_of_code_2024.so`, with
+//!
+//! ```text
+//! cargo rustc --release --lib --features ffi --crate-type cdylib
+//! ```
+//!
+//! `aoc_solve` solves one part of a day of the latest year from an input of UTF-8 bytes and
+//! writes the answer into `out`. Like a plugin solver it returns the answer's length in bytes,
+//! which may be more than `out_len` to ask for a bigger buffer. On failure it returns one of
+//! the negative `AOC_ERR_*` codes, and `aoc_last_error` gives the message. Panics are caught
+//! before they reach the caller, and print nothing to its stderr.
+
+use crate::{advent, answer::Answer, solution::catch_panic, spec};
+use std::{
+    cell::RefCell,
+    panic, ptr, slice,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Once,
+    },
+};
+
+pub const AOC_ERR_UNKNOWN_DAY: isize = -1;
+pub const AOC_ERR_UNKNOWN_PART: isize = -2;
+/// The input pointer was null or the input is not UTF-8.
+pub const AOC_ERR_INVALID_INPUT: isize = -3;
+/// The day panicked while parsing the input.
+pub const AOC_ERR_PARSE: isize = -4;
+/// The day panicked while preparing the input or solving the part.
+pub const AOC_ERR_PANIC: isize = -5;
+/// The part has no answer for this input, e.g. because the guard walks in a loop.
+pub const AOC_ERR_NO_ANSWER: isize = -6;
+
+thread_local! {
+    static LAST_ERROR: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+/// How many calls to `aoc_solve` are running, on any thread of the host. Panics while there are
+/// any are not reported. It is process-wide, since the days that solve in parallel panic on
+/// rayon's worker threads rather than the caller's.
+static SOLVING: AtomicUsize = AtomicUsize::new(0);
+
+static QUIET_PANICS: Once = Once::new();
+
+/// Wraps the panic hook so that it stays quiet about panics inside `aoc_solve`, which are
+/// returned as errors instead. Panics in the process while no call is running are reported as
+/// before.
+fn quiet_panics() {
+    QUIET_PANICS.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if SOLVING.load(Ordering::SeqCst) == 0 {
+                hook(info);
+            }
+        }));
+    });
+}
+
+/// # Safety
+///
+/// `input` must point to `input_len` readable bytes, or be null if `input_len` is 0. `out` must
+/// point to `out_len` writable bytes, or be null if `out_len` is 0.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    day: u32,
+    part: u32,
+    input: *const u8,
+    input_len: usize,
+    out: *mut u8,
+    out_len: usize,
+) -> isize {
+    let input = match (input.is_null(), input_len) {
+        (true, 0) => &[],
+        (true, _) => return fail(AOC_ERR_INVALID_INPUT, "input is null".into()),
+        (false, _) => slice::from_raw_parts(input, input_len),
+    };
+
+    quiet_panics();
+    SOLVING.fetch_add(1, Ordering::SeqCst);
+    let result = solve(day, part, input);
+    SOLVING.fetch_sub(1, Ordering::SeqCst);
+
+    match result {
+        Ok(answer) => copy_out(answer.to_string().as_bytes(), out, out_len),
+        Err((code, message)) => fail(code, message),
+    }
+}
+
+/// Writes the message of the last failed `aoc_solve` on this thread into `out`, returning its
+/// length in the same way.
+///
+/// # Safety
+///
+/// `out` must point to `out_len` writable bytes, or be null if `out_len` is 0.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_last_error(out: *mut u8, out_len: usize) -> isize {
+    LAST_ERROR.with(|last_error| copy_out(last_error.borrow().as_bytes(), out, out_len))
+}
+
+fn solve(day: u32, part: u32, input: &[u8]) -> Result<Answer, (isize, String)> {
+    let year = advent::days().map(|day| day.year).max();
+    let Some(day) = year.and_then(|year| advent::find_day(year, day)) else {
+        return Err((AOC_ERR_UNKNOWN_DAY, format!("Unknown day: {day}")));
+    };
+    if !spec::PARTS.contains(&part) {
+        return Err((AOC_ERR_UNKNOWN_PART, format!("Invalid part: {part}")));
+    }
+    let input = std::str::from_utf8(input)
+        .map_err(|e| (AOC_ERR_INVALID_INPUT, format!("Input is not UTF-8: {e}")))?;
+
+    // Parsing on its own first tells a bad input from a bug in a part, at the cost of parsing
+    // twice.
+    catch_panic(|| (day.parse)(input)).map_err(|e| (AOC_ERR_PARSE, e))?;
+    let day_run = catch_panic(|| (day.run)(input, &[part])).map_err(|e| (AOC_ERR_PANIC, e))?;
+    match day_run.parts.into_iter().next() {
+        Some(part_run) => part_run.answer.map_err(|e| (AOC_ERR_NO_ANSWER, e)),
+        None => Err((AOC_ERR_UNKNOWN_PART, format!("Invalid part: {part}"))),
+    }
+}
+
+fn fail(code: isize, message: String) -> isize {
+    LAST_ERROR.with(|last_error| *last_error.borrow_mut() = message);
+    code
+}
+
+unsafe fn copy_out(bytes: &[u8], out: *mut u8, out_len: usize) -> isize {
+    if !bytes.is_empty() && !out.is_null() && bytes.len() <= out_len {
+        ptr::copy_nonoverlapping(bytes.as_ptr(), out, bytes.len());
+    }
+    bytes.len() as isize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(day: u32, part: u32, input: &[u8], out_len: usize) -> (isize, String) {
+        let mut out = vec![0u8; out_len];
+        let len = unsafe {
+            aoc_solve(
+                day,
+                part,
+                input.as_ptr(),
+                input.len(),
+                out.as_mut_ptr(),
+                out.len(),
+            )
+        };
+        out.truncate(len.clamp(0, out_len as isize) as usize);
+        (len, String::from_utf8(out).unwrap())
+    }
+
+    fn last_error() -> String {
+        let mut out = vec![0u8; 256];
+        let len = unsafe { aoc_last_error(out.as_mut_ptr(), out.len()) };
+        out.truncate(len as usize);
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_aoc_solve() {
+        let input = b"3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+        assert_eq!(call(1, 1, input, 16), (2, "11".into()));
+        assert_eq!(call(1, 2, input, 16), (2, "31".into()));
+        assert_eq!(call(1, 2, input, 1).0, 2);
+
+        assert_eq!(call(26, 1, input, 16).0, AOC_ERR_UNKNOWN_DAY);
+        assert_eq!(last_error(), "Unknown day: 26");
+        assert_eq!(call(1, 3, input, 16).0, AOC_ERR_UNKNOWN_PART);
+        assert_eq!(call(1, 1, b"\xff", 16).0, AOC_ERR_INVALID_INPUT);
+        assert_eq!(call(1, 1, b"1 x\n", 16).0, AOC_ERR_PARSE);
+        assert!(last_error().contains("ParseIntError"));
+        assert_eq!(
+            call(6, 1, b".#..\n...#\n#^..\n..#.\n", 16).0,
+            AOC_ERR_NO_ANSWER
+        );
+        assert_eq!(last_error(), "The guard walks in a loop");
+    }
+
+    #[test]
+    fn test_null_out() {
+        // A fresh thread has had no error, so there is nothing to write into the null buffer.
+        let len = std::thread::spawn(|| unsafe { aoc_last_error(ptr::null_mut(), 0) })
+            .join()
+            .unwrap();
+        assert_eq!(len, 0);
+
+        let input = b"3   4\n";
+        let len = unsafe { aoc_solve(1, 1, input.as_ptr(), input.len(), ptr::null_mut(), 0) };
+        assert_eq!(len, 1);
+    }
+}
@@ -0,0 +1,59 @@
+//! Example inputs with known answers, kept in `fixtures/<year>/<day>/<name>.txt` next to an
+//! `answers.toml` that records them as `<name>.partM = answer`.
+
+use crate::verify::{Answers, ANSWERS_FILE};
+use std::{fs, io, path::Path};
+
+pub const FIXTURES_DIR: &str = "fixtures";
+
+pub struct Fixture {
+    pub name: String,
+    pub input: String,
+}
+
+/// Reads a day's fixtures in name order, with their answers. A day without a fixture
+/// directory has no fixtures.
+pub fn load(fixtures_dir: &Path, year: u32, day: &str) -> Result<(Vec<Fixture>, Answers), String> {
+    let day_dir = fixtures_dir.join(year.to_string()).join(day);
+    let entries = match fs::read_dir(&day_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok((vec![], Answers::default())),
+        Err(e) => return Err(format!("Could not read {}: {e}", day_dir.display())),
+    };
+
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension() == Some("txt".as_ref()))
+        .collect();
+    paths.sort();
+
+    let fixtures = paths
+        .iter()
+        .map(|path| {
+            let input = fs::read_to_string(path)
+                .map_err(|e| format!("Could not read {}: {e}", path.display()))?;
+            let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+            Ok(Fixture { name, input })
+        })
+        .collect::<Result<_, String>>()?;
+    let answers = Answers::load(&day_dir.join(ANSWERS_FILE))?;
+
+    Ok((fixtures, answers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load() {
+        let (fixtures, answers) = load(Path::new(FIXTURES_DIR), 2024, "day8").unwrap();
+        let names: Vec<&str> = fixtures.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["example", "example2"]);
+        assert!(fixtures[1].input.starts_with("T........."));
+        assert_eq!(answers.get("example2", 2).unwrap(), &9);
+
+        let (fixtures, _) = load(Path::new(FIXTURES_DIR), 2024, "day99").unwrap();
+        assert!(fixtures.is_empty());
+    }
+}
@@ -0,0 +1,435 @@
+//! Mutation fuzzing of the days' parsers and solvers. Each day's fixtures are edited at random
+//! in the ways inputs get broken, by dropping lines, swapping characters, inserting digits or
+//! changing the width of grid rows, and each mutant is parsed and solved under a time limit.
+//! A panic or a hang is a crash, saved under [`CRASHES_DIR`] once its input has been shrunk to
+//! as little as still crashes in the same place.
+
+use crate::{
+    minimize::{self, Shape},
+    rng::Rng,
+    solution::{catch_panic, DayRun},
+};
+use std::{
+    cell::RefCell,
+    fmt, fs, io, panic,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Mutex, MutexGuard, PoisonError,
+    },
+    thread,
+    time::Duration,
+};
+
+pub const CRASHES_DIR: &str = "fuzz/crashes";
+pub const DEFAULT_RUNS: usize = 1000;
+pub const DEFAULT_SEED: u64 = 2024;
+pub const DEFAULT_TIME_LIMIT: Duration = Duration::from_secs(5);
+/// Each mutant is made by between one and this many mutations.
+const MAX_MUTATIONS: u64 = 4;
+/// Characters put into inputs: the syntax of every day, and some that no day expects.
+const ALPHABET: &[char] = &[
+    '0', '1', '5', '9', '.', '#', '^', 'X', 'M', 'A', 'S', ':', '|', ',', ' ', '-', '(', ')', '\n',
+    '\t', 'é',
+];
+
+pub type Run = fn(&str, &[u32]) -> DayRun;
+
+#[derive(Clone, Copy, Debug)]
+enum Mutation {
+    DropLine,
+    DuplicateLine,
+    SwapChars,
+    InsertDigit,
+    DeleteChar,
+    ReplaceChar,
+    ChangeWidth,
+}
+
+const MUTATIONS: &[Mutation] = &[
+    Mutation::DropLine,
+    Mutation::DuplicateLine,
+    Mutation::SwapChars,
+    Mutation::InsertDigit,
+    Mutation::DeleteChar,
+    Mutation::ReplaceChar,
+    Mutation::ChangeWidth,
+];
+
+/// `input` after a few random mutations.
+pub fn mutate(input: &str, rng: &mut Rng) -> String {
+    let mut input = input.to_string();
+    for _ in 0..rng.range(1, MAX_MUTATIONS + 1) {
+        input = apply(*rng.choose(MUTATIONS), &input, rng);
+    }
+    input
+}
+
+/// A position in `0..len`, or `None` if there is none.
+fn pick(rng: &mut Rng, len: usize) -> Option<usize> {
+    (len > 0).then(|| rng.range(0, len as u64) as usize)
+}
+
+fn apply(mutation: Mutation, input: &str, rng: &mut Rng) -> String {
+    let mut lines: Vec<String> = input.split_inclusive('\n').map(String::from).collect();
+    let mut chars: Vec<char> = input.chars().collect();
+
+    match mutation {
+        Mutation::DropLine => {
+            if let Some(i) = pick(rng, lines.len()) {
+                lines.remove(i);
+            }
+            return lines.concat();
+        }
+        Mutation::DuplicateLine => {
+            if let Some(i) = pick(rng, lines.len()) {
+                lines.insert(i, lines[i].clone());
+            }
+            return lines.concat();
+        }
+        Mutation::ChangeWidth => {
+            if let Some(i) = pick(rng, lines.len()) {
+                let line = &lines[i];
+                let (row, end) = match line.strip_suffix('\n') {
+                    Some(row) => (row, "\n"),
+                    None => (line.as_str(), ""),
+                };
+                let mut row: Vec<char> = row.chars().collect();
+                if rng.chance(1, 2) {
+                    row.pop();
+                } else {
+                    row.push(row.last().copied().unwrap_or('.'));
+                }
+                lines[i] = row.into_iter().chain(end.chars()).collect();
+            }
+            return lines.concat();
+        }
+        Mutation::SwapChars => {
+            if let (Some(i), Some(j)) = (pick(rng, chars.len()), pick(rng, chars.len())) {
+                chars.swap(i, j);
+            }
+        }
+        Mutation::InsertDigit => {
+            let i = rng.range(0, chars.len() as u64 + 1) as usize;
+            let digit = char::from_digit(rng.range(0, 10) as u32, 10).unwrap();
+            chars.insert(i, digit);
+        }
+        Mutation::DeleteChar => {
+            if let Some(i) = pick(rng, chars.len()) {
+                chars.remove(i);
+            }
+        }
+        Mutation::ReplaceChar => {
+            if let Some(i) = pick(rng, chars.len()) {
+                chars[i] = *rng.choose(ALPHABET);
+            }
+        }
+    }
+    chars.into_iter().collect()
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Failure {
+    /// `location` is where the panic happened, as `file:line:column`.
+    Panic {
+        location: String,
+        message: String,
+    },
+    Timeout,
+}
+
+impl Failure {
+    /// Crashes in the same place are the same bug, whatever their messages say.
+    pub fn signature(&self) -> &str {
+        match self {
+            Failure::Panic { location, .. } => location,
+            Failure::Timeout => "timeout",
+        }
+    }
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::Panic { location, message } => write!(f, "panicked at {location}: {message}"),
+            Failure::Timeout => write!(f, "timed out"),
+        }
+    }
+}
+
+/// Whether a check is running. It is process-wide, since the days that solve in parallel panic
+/// on rayon's worker threads, and the panic reaches the check's thread through
+/// `resume_unwind`, which does not run the hook again.
+static CHECKING: AtomicBool = AtomicBool::new(false);
+/// Where the first panic on any thread during the running check happened, recorded by the hook
+/// from [`set_panic_hook`].
+static PANIC_LOCATION: Mutex<Option<String>> = Mutex::new(None);
+
+thread_local! {
+    /// Where the last panic on this thread happened. A check prefers its own thread's panic to
+    /// the process-wide one, which an unrelated thread may have got to first.
+    static THREAD_PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+fn panic_location() -> MutexGuard<'static, Option<String>> {
+    PANIC_LOCATION
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Wraps the panic hook so that panics during a check print nothing and record where they
+/// happened, so that crashes can be told apart. Other panics are reported as before.
+pub fn set_panic_hook() {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if !CHECKING.load(Ordering::SeqCst) {
+            return hook(info);
+        }
+        let location = info.location().map(|location| location.to_string());
+        panic_location().get_or_insert_with(|| location.clone().unwrap_or_default());
+        THREAD_PANIC_LOCATION.with(|panic_location| *panic_location.borrow_mut() = location);
+    }));
+}
+
+/// Runs a day on one input after another under a time limit. A thread that times out cannot
+/// be stopped, so after the first timeout nothing more is run and every check times out. That
+/// way at most one thread is left running, and it does not skew the timing of later checks.
+pub struct Checker<'a> {
+    run: Run,
+    parts: &'a [u32],
+    time_limit: Duration,
+    /// The input that timed out, if one has.
+    pub hung: Option<String>,
+}
+
+impl<'a> Checker<'a> {
+    pub fn new(run: Run, parts: &'a [u32], time_limit: Duration) -> Self {
+        Checker {
+            run,
+            parts,
+            time_limit,
+            hung: None,
+        }
+    }
+
+    /// Parses `input` and solves the parts of it in another thread.
+    pub fn check(&mut self, input: &str) -> Result<(), Failure> {
+        if self.hung.is_some() {
+            return Err(Failure::Timeout);
+        }
+        let result = check(self.run, input, self.parts, self.time_limit);
+        if result == Err(Failure::Timeout) {
+            self.hung = Some(input.to_string());
+        }
+        result
+    }
+}
+
+/// Parses `input` and solves `parts` of it in another thread, which is given up on after
+/// `time_limit` and left running.
+fn check(run: Run, input: &str, parts: &[u32], time_limit: Duration) -> Result<(), Failure> {
+    let (sender, receiver) = mpsc::channel();
+    let (input, parts) = (input.to_string(), parts.to_vec());
+    *panic_location() = None;
+    CHECKING.store(true, Ordering::SeqCst);
+    thread::spawn(move || {
+        let result = catch_panic(|| run(&input, &parts)).map_err(|message| Failure::Panic {
+            location: THREAD_PANIC_LOCATION
+                .with(RefCell::take)
+                .or_else(|| panic_location().take())
+                .filter(|location| !location.is_empty())
+                .unwrap_or_else(|| "unknown".into()),
+            message,
+        });
+        // Nobody is listening any more if the check timed out.
+        let _ = sender.send(result.map(|_| ()));
+    });
+
+    let result = receiver
+        .recv_timeout(time_limit)
+        .unwrap_or(Err(Failure::Timeout));
+    // A thread left running after a timeout reports its panics again.
+    CHECKING.store(false, Ordering::SeqCst);
+    result
+}
+
+pub struct Crash {
+    pub failure: Failure,
+    pub input: String,
+}
+
+impl Crash {
+    /// Named after where it crashed, e.g. `day4-52-9.txt`.
+    fn file_name(&self) -> String {
+        let Failure::Panic { location, .. } = &self.failure else {
+            return "timeout.txt".into();
+        };
+        let (file, position) = location.split_once(':').unwrap_or((location, ""));
+        let stem = Path::new(file).file_stem().unwrap_or_default();
+        match position {
+            "" => format!("{}.txt", stem.to_string_lossy()),
+            _ => format!(
+                "{}-{}.txt",
+                stem.to_string_lossy(),
+                position.replace(':', "-")
+            ),
+        }
+    }
+}
+
+/// Checks up to `runs` mutants of `seeds` with `checker`, returning the first crash in each
+/// place with its input shrunk. Fuzzing stops at the first timeout, whether of a mutant or of
+/// an input tried while shrinking, and the input that hung is the last crash.
+pub fn fuzz(
+    checker: &mut Checker,
+    shape: Shape,
+    seeds: &[String],
+    runs: usize,
+    rng: &mut Rng,
+) -> Vec<Crash> {
+    let mut crashes: Vec<Crash> = vec![];
+    if seeds.is_empty() {
+        return crashes;
+    }
+
+    for _ in 0..runs {
+        let seed: &String = rng.choose(seeds);
+        let input = mutate(seed, rng);
+        let Err(failure) = checker.check(&input) else {
+            continue;
+        };
+        if failure == Failure::Timeout {
+            break;
+        }
+        if crashes
+            .iter()
+            .any(|crash| crash.failure.signature() == failure.signature())
+        {
+            continue;
+        }
+
+        // Shrinking in the day's own units first makes big cuts quickly. Crashes are often in
+        // parsing, though, so a character can matter too.
+        let mut fails = |candidate: &str| {
+            matches!(checker.check(candidate),
+                Err(f) if f.signature() == failure.signature())
+        };
+        let input = minimize::minimize(&input, shape, &mut fails);
+        let input = minimize::minimize(&input, Shape::Text, fails);
+        // The message can change as the input shrinks, so it is taken from the input saved.
+        let failure = match checker.check(&input) {
+            Err(Failure::Panic { location, message }) => Failure::Panic { location, message },
+            _ => failure,
+        };
+        crashes.push(Crash { failure, input });
+        if checker.hung.is_some() {
+            break;
+        }
+    }
+
+    if let Some(input) = &checker.hung {
+        crashes.push(Crash {
+            failure: Failure::Timeout,
+            input: input.clone(),
+        });
+    }
+    crashes
+}
+
+/// Writes the crash's input into `dir`, returning the path.
+pub fn save(crash: &Crash, dir: &Path) -> io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+    let path = dir.join(crash.file_name());
+    fs::write(&path, &crash.input)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fragile(input: &str, _parts: &[u32]) -> DayRun {
+        if input.contains('#') {
+            panic!("found a wall");
+        }
+        if input.contains('~') {
+            thread::sleep(Duration::from_secs(1));
+        }
+        DayRun {
+            phases: vec![],
+            parts: vec![],
+        }
+    }
+
+    /// Panics on another thread, as a day that solves with rayon does, and passes the panic on.
+    fn fragile_worker(input: &str, parts: &[u32]) -> DayRun {
+        let input = input.to_string();
+        let worker = thread::spawn(move || {
+            if input.contains('#') {
+                panic!("a worker found a wall");
+            }
+        });
+        if let Err(payload) = worker.join() {
+            panic::resume_unwind(payload);
+        }
+        fragile("", parts)
+    }
+
+    #[test]
+    fn test_mutate() {
+        let input = "12 34\n56 78\n";
+        let mutants: Vec<String> = (0..100)
+            .map(|seed| mutate(input, &mut Rng::new(seed)))
+            .collect();
+        assert!(mutants.iter().filter(|mutant| *mutant != input).count() > 90);
+        assert_eq!(mutate(input, &mut Rng::new(3)), mutants[3]);
+        for seed in 0..100 {
+            mutate("", &mut Rng::new(seed));
+        }
+    }
+
+    #[test]
+    fn test_fuzz() {
+        // Only panics in checked threads are kept quiet, and the hook is put back afterwards.
+        let previous = panic::take_hook();
+        set_panic_hook();
+        let seeds = vec!["....\n....\n....\n".to_string()];
+        let time_limit = Duration::from_millis(100);
+        let mut checker = Checker::new(fragile, &[1], time_limit);
+        let crashes = fuzz(&mut checker, Shape::Grid, &seeds, 300, &mut Rng::new(1));
+        let hung = checker.hung.is_some();
+
+        let mut checker = Checker::new(fragile_worker, &[1], time_limit);
+        let worker_crash = checker.check("#");
+
+        let mut checker = Checker::new(fragile, &[1], time_limit);
+        let timeout = checker.check("~");
+        let after_timeout = checker.check("#");
+        panic::set_hook(previous);
+
+        // A panic on a worker thread is found where it happened, not reported as unknown.
+        let Err(Failure::Panic { location, message }) = worker_crash else {
+            panic!("expected a panic, got {worker_crash:?}");
+        };
+        assert!(location.starts_with("src/fuzz.rs:"), "{location}");
+        assert_eq!(message, "a worker found a wall");
+
+        let panic = crashes
+            .iter()
+            .find(|crash| crash.failure.to_string().ends_with(": found a wall"))
+            .unwrap();
+        assert_eq!(panic.input, "#");
+        assert!(panic.failure.signature().starts_with("src/fuzz.rs:"));
+        assert!(panic.file_name().starts_with("fuzz-"));
+
+        // A timeout ends the run, and is its last crash.
+        assert_eq!(crashes.last().unwrap().failure == Failure::Timeout, hung);
+        assert_eq!(timeout, Err(Failure::Timeout));
+        assert_eq!(after_timeout, Err(Failure::Timeout));
+        let crash = Crash {
+            failure: Failure::Timeout,
+            input: "~".into(),
+        };
+        assert_eq!(crash.file_name(), "timeout.txt");
+    }
+}
@@ -0,0 +1,430 @@
+//! Graph algorithms, generic over the node type.
+//!
+//! Graphs are given either as an [`AdjacencyList`] or implicitly as a `successors` function,
+//! which lets grids and other computed graphs be searched without building them first. Not
+//! every day uses every algorithm.
+
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet, VecDeque},
+    fmt,
+    hash::Hash,
+    ops::Add,
+};
+
+/// A directed graph stored as the list of successors of each node.
+#[derive(Clone, Debug)]
+pub struct AdjacencyList<N> {
+    edges: HashMap<N, Vec<N>>,
+}
+
+impl<N: Clone + Eq + Hash> AdjacencyList<N> {
+    pub fn add_edge(&mut self, from: N, to: N) {
+        self.edges.entry(from).or_default().push(to);
+    }
+
+    pub fn successors(&self, node: &N) -> &[N] {
+        self.edges.get(node).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn has_edge(&self, from: &N, to: &N) -> bool {
+        self.successors(from).contains(to)
+    }
+}
+
+impl<N> Default for AdjacencyList<N> {
+    fn default() -> Self {
+        AdjacencyList {
+            edges: HashMap::new(),
+        }
+    }
+}
+
+impl<N: Clone + Eq + Hash> FromIterator<(N, N)> for AdjacencyList<N> {
+    fn from_iter<T: IntoIterator<Item = (N, N)>>(edges: T) -> Self {
+        let mut graph = AdjacencyList::default();
+        for (from, to) in edges {
+            graph.add_edge(from, to);
+        }
+        graph
+    }
+}
+
+/// The nodes of a cycle found while sorting, with the first node repeated at the end.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cycle<N>(pub Vec<N>);
+
+impl<N: fmt::Display> fmt::Display for Cycle<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nodes: Vec<String> = self.0.iter().map(|node| node.to_string()).collect();
+        write!(f, "{}", nodes.join(" -> "))
+    }
+}
+
+/// The distance in edges from `start` to every node reachable from it.
+pub fn bfs<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        let distance = distances[&node];
+        for next in successors(&node) {
+            if let Entry::Vacant(entry) = distances.entry(next.clone()) {
+                entry.insert(distance + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    distances
+}
+
+/// Every node reachable from `start`, in depth-first preorder.
+pub fn dfs<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> Vec<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut visited = HashSet::new();
+    let mut order = vec![];
+    let mut stack = vec![start];
+
+    while let Some(node) = stack.pop() {
+        if !visited.insert(node.clone()) {
+            continue;
+        }
+        let mut next: Vec<N> = successors(&node).into_iter().collect();
+        next.reverse();
+        stack.extend(next);
+        order.push(node);
+    }
+
+    order
+}
+
+/// Orders `nodes` so that every edge between them points forwards. Edges to nodes outside
+/// `nodes` are dropped, so a path between two of them through an outside node does not order
+/// them. A node repeated in `nodes` appears once in the order. Fails with one of the cycles if
+/// there is no such order.
+pub fn topo_sort<N, I>(nodes: &[N], mut successors: impl FnMut(&N) -> I) -> Result<Vec<N>, Cycle<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    enum Mark {
+        InProgress,
+        Done,
+    }
+
+    let wanted: HashSet<&N> = nodes.iter().collect();
+    let mut marks: HashMap<N, Mark> = HashMap::new();
+    let mut sorted = vec![];
+
+    for root in nodes {
+        if marks.contains_key(root) {
+            continue;
+        }
+
+        // Each entry is a node on the current path and its unvisited successors, reversed so
+        // that they are visited in order.
+        let mut pending_successors = |node: &N| {
+            let mut pending: Vec<N> = successors(node).into_iter().collect();
+            pending.reverse();
+            pending
+        };
+        marks.insert(root.clone(), Mark::InProgress);
+        let mut path = vec![(root.clone(), pending_successors(root))];
+
+        while let Some((node, pending)) = path.last_mut() {
+            let Some(next) = pending.pop() else {
+                marks.insert(node.clone(), Mark::Done);
+                sorted.push(node.clone());
+                path.pop();
+                continue;
+            };
+            if !wanted.contains(&next) {
+                continue;
+            }
+
+            match marks.get(&next) {
+                Some(Mark::Done) => {}
+                Some(Mark::InProgress) => {
+                    let start = path.iter().position(|(node, _)| *node == next).unwrap();
+                    let mut cycle: Vec<N> =
+                        path[start..].iter().map(|(node, _)| node.clone()).collect();
+                    cycle.push(next);
+                    return Err(Cycle(cycle));
+                }
+                None => {
+                    marks.insert(next.clone(), Mark::InProgress);
+                    let next_successors = pending_successors(&next);
+                    path.push((next, next_successors));
+                }
+            }
+        }
+    }
+
+    sorted.reverse();
+    Ok(sorted)
+}
+
+/// The cheapest path from `start` to a node satisfying `is_goal`, and its cost.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+/// Like [`dijkstra`], but guided by `heuristic`, which must be consistent: it never
+/// overestimates the remaining cost to a goal, and never falls by more than a step's cost from
+/// a node to its successor. Nodes are not revisited once expanded, so a heuristic that is only
+/// admissible can give a path that is not the cheapest.
+pub fn astar<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    // Nodes are numbered in the order they are found so the heap only has to order numbers.
+    let mut nodes = vec![start.clone()];
+    let mut indices = HashMap::from([(start.clone(), 0)]);
+    let mut parents = vec![usize::MAX];
+    let mut costs = vec![C::default()];
+    let mut done = vec![false];
+
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), 0))]);
+
+    while let Some(Reverse((_, index))) = heap.pop() {
+        if done[index] {
+            continue;
+        }
+        done[index] = true;
+
+        let node = nodes[index].clone();
+        if is_goal(&node) {
+            let mut path = vec![];
+            let mut current = index;
+            while current != usize::MAX {
+                path.push(nodes[current].clone());
+                current = parents[current];
+            }
+            path.reverse();
+            return Some((path, costs[index]));
+        }
+
+        for (next, step_cost) in successors(&node) {
+            let cost = costs[index] + step_cost;
+            let next_index = match indices.get(&next) {
+                Some(&next_index) if done[next_index] || costs[next_index] <= cost => continue,
+                Some(&next_index) => next_index,
+                None => {
+                    nodes.push(next.clone());
+                    parents.push(usize::MAX);
+                    costs.push(cost);
+                    done.push(false);
+                    indices.insert(next.clone(), nodes.len() - 1);
+                    nodes.len() - 1
+                }
+            };
+            parents[next_index] = index;
+            costs[next_index] = cost;
+            heap.push(Reverse((cost + heuristic(&next), next_index)));
+        }
+    }
+
+    None
+}
+
+/// The number of distinct paths from `start` to nodes satisfying `is_goal`. The graph must be
+/// acyclic. A path stops at the first goal it reaches.
+pub fn count_paths<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> usize
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    fn count<N: Clone + Eq + Hash, I: IntoIterator<Item = N>>(
+        node: &N,
+        successors: &mut impl FnMut(&N) -> I,
+        is_goal: &mut impl FnMut(&N) -> bool,
+        counts: &mut HashMap<N, usize>,
+    ) -> usize {
+        if is_goal(node) {
+            return 1;
+        }
+        if let Some(&paths) = counts.get(node) {
+            return paths;
+        }
+
+        let paths = successors(node)
+            .into_iter()
+            .map(|next| count(&next, successors, is_goal, counts))
+            .sum();
+        counts.insert(node.clone(), paths);
+        paths
+    }
+
+    count(&start, &mut successors, &mut is_goal, &mut HashMap::new())
+}
+
+/// Groups `nodes` and the nodes reachable from them into connected components, treating each
+/// edge from `neighbors` as going both ways. Components and their nodes are in the order they
+/// are first found.
+pub fn connected_components<N, I>(nodes: &[N], mut neighbors: impl FnMut(&N) -> I) -> Vec<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    // An edge can only be followed backwards once it has been found forwards.
+    let mut undirected = AdjacencyList::default();
+    let mut seen = HashSet::new();
+    for node in nodes {
+        if seen.contains(node) {
+            continue;
+        }
+        seen.extend(dfs(node.clone(), |node| {
+            let next: Vec<N> = neighbors(node).into_iter().collect();
+            for next in &next {
+                undirected.add_edge(node.clone(), next.clone());
+                undirected.add_edge(next.clone(), node.clone());
+            }
+            next
+        }));
+    }
+
+    let mut seen = HashSet::new();
+    let mut components = vec![];
+    for node in nodes {
+        if seen.contains(node) {
+            continue;
+        }
+        let component = dfs(node.clone(), |node| undirected.successors(node).to_vec());
+        seen.extend(component.iter().cloned());
+        components.push(component);
+    }
+
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diamond() -> AdjacencyList<char> {
+        [('a', 'b'), ('a', 'c'), ('b', 'd'), ('c', 'd'), ('d', 'e')]
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn test_bfs_and_dfs() {
+        let graph = diamond();
+        let distances = bfs('a', |n| graph.successors(n).to_vec());
+        assert_eq!(distances[&'a'], 0);
+        assert_eq!(distances[&'d'], 2);
+        assert_eq!(distances[&'e'], 3);
+        assert_eq!(distances.len(), 5);
+
+        assert_eq!(
+            dfs('a', |n| graph.successors(n).to_vec()),
+            vec!['a', 'b', 'd', 'e', 'c']
+        );
+    }
+
+    #[test]
+    fn test_topo_sort() {
+        let graph = diamond();
+        let sorted =
+            topo_sort(&['e', 'd', 'c', 'b', 'a'], |n| graph.successors(n).to_vec()).unwrap();
+        for (from, to) in [('a', 'b'), ('a', 'c'), ('b', 'd'), ('c', 'd'), ('d', 'e')] {
+            let position = |n| sorted.iter().position(|&m| m == n).unwrap();
+            assert!(position(from) < position(to));
+        }
+
+        let sorted = topo_sort(&['d', 'a'], |n| graph.successors(n).to_vec()).unwrap();
+        assert_eq!(sorted, vec!['a', 'd']);
+        let sorted = topo_sort(&['d', 'a', 'd'], |n| graph.successors(n).to_vec()).unwrap();
+        assert_eq!(sorted, vec!['a', 'd']);
+
+        let mut cyclic = diamond();
+        cyclic.add_edge('e', 'b');
+        let cycle = topo_sort(&['a', 'b', 'c', 'd', 'e'], |n| {
+            cyclic.successors(n).to_vec()
+        })
+        .unwrap_err();
+        assert_eq!(cycle.to_string(), "b -> d -> e -> b");
+    }
+
+    #[test]
+    fn test_dijkstra_and_astar() {
+        let successors = |&(x, y): &(i32, i32)| {
+            [(x + 1, y), (x, y + 1)]
+                .into_iter()
+                .filter(|&(x, y)| x <= 3 && y <= 3)
+                .map(|(x, y)| ((x, y), if y == 0 { 1 } else { 5 }))
+                .collect::<Vec<_>>()
+        };
+
+        let (path, cost) = dijkstra((0, 0), successors, |&n| n == (3, 3)).unwrap();
+        assert_eq!(cost, 18);
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&(3, 3)));
+        assert_eq!(path[3], (3, 0));
+
+        let heuristic = |&(x, y): &(i32, i32)| (3 - x) + (3 - y);
+        let (_, astar_cost) = astar((0, 0), successors, heuristic, |&n| n == (3, 3)).unwrap();
+        assert_eq!(astar_cost, cost);
+
+        assert_eq!(dijkstra((0, 0), successors, |&n| n == (9, 9)), None);
+    }
+
+    #[test]
+    fn test_count_paths() {
+        let graph = diamond();
+        assert_eq!(
+            count_paths('a', |n| graph.successors(n).to_vec(), |&n| n == 'e'),
+            2
+        );
+        assert_eq!(
+            count_paths('a', |n| graph.successors(n).to_vec(), |&n| n == 'c'),
+            1
+        );
+        assert_eq!(
+            count_paths('e', |n| graph.successors(n).to_vec(), |&n| n == 'a'),
+            0
+        );
+    }
+
+    #[test]
+    fn test_connected_components() {
+        let edges = [(1, 2), (2, 3), (4, 5)];
+        let graph: AdjacencyList<u32> = edges.iter().flat_map(|&(a, b)| [(a, b), (b, a)]).collect();
+        let components =
+            connected_components(&[1, 2, 3, 4, 5, 6], |n| graph.successors(n).to_vec());
+        assert_eq!(components, vec![vec![1, 2, 3], vec![4, 5], vec![6]]);
+
+        // Edges only from 1 and 3 still join them to 2, which is listed first.
+        let graph: AdjacencyList<u32> = [(1, 2), (3, 2)].into_iter().collect();
+        let components = connected_components(&[2, 1, 3, 4], |n| graph.successors(n).to_vec());
+        assert_eq!(components, vec![vec![2, 1, 3], vec![4]]);
+    }
+}
@@ -0,0 +1,119 @@
+use std::{
+    fmt,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Read},
+    path::PathBuf,
+};
+
+/// Inputs bigger than this are streamed by days that support it instead of being read into
+/// memory whole.
+pub const STREAM_THRESHOLD: u64 = 256 * 1024 * 1024;
+
+/// Where a puzzle input comes from, read either whole, by line or in chunks of bytes.
+#[derive(Clone, Debug)]
+pub enum InputSource {
+    File(PathBuf),
+    Text(String),
+}
+
+impl InputSource {
+    /// The size of the input in bytes.
+    pub fn size(&self) -> io::Result<u64> {
+        match self {
+            InputSource::File(path) => Ok(fs::metadata(path)?.len()),
+            InputSource::Text(text) => Ok(text.len() as u64),
+        }
+    }
+
+    pub fn read_to_string(&self) -> io::Result<String> {
+        match self {
+            InputSource::File(path) => fs::read_to_string(path),
+            InputSource::Text(text) => Ok(text.clone()),
+        }
+    }
+
+    pub fn reader(&self) -> io::Result<Box<dyn BufRead + '_>> {
+        match self {
+            InputSource::File(path) => Ok(Box::new(BufReader::new(File::open(path)?))),
+            InputSource::Text(text) => Ok(Box::new(text.as_bytes())),
+        }
+    }
+
+    /// The input's lines without their line endings, read one at a time.
+    pub fn lines(&self) -> io::Result<impl Iterator<Item = io::Result<String>> + '_> {
+        Ok(self.reader()?.lines())
+    }
+
+    /// The input in chunks of `chunk_size` bytes, except for a shorter last chunk. Chunks may
+    /// split lines and UTF-8 characters.
+    pub fn chunks(&self, chunk_size: usize) -> io::Result<Chunks<'_>> {
+        Ok(Chunks {
+            reader: self.reader()?,
+            chunk_size,
+        })
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Text(_) => write!(f, "input text"),
+        }
+    }
+}
+
+pub struct Chunks<'a> {
+    reader: Box<dyn BufRead + 'a>,
+    chunk_size: usize,
+}
+
+impl Iterator for Chunks<'_> {
+    type Item = io::Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut chunk = Vec::with_capacity(self.chunk_size);
+        match (&mut self.reader)
+            .take(self.chunk_size as u64)
+            .read_to_end(&mut chunk)
+        {
+            Ok(0) => None,
+            Ok(_) => Some(Ok(chunk)),
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_source() {
+        let source = InputSource::Text("ab\ncd\n\nef".into());
+        assert_eq!(source.size().unwrap(), 9);
+
+        let lines: Vec<String> = source.lines().unwrap().map(Result::unwrap).collect();
+        assert_eq!(lines, vec!["ab", "cd", "", "ef"]);
+
+        let chunks: Vec<Vec<u8>> = source.chunks(4).unwrap().map(Result::unwrap).collect();
+        assert_eq!(
+            chunks,
+            vec![b"ab\nc".to_vec(), b"d\n\ne".to_vec(), b"f".to_vec()]
+        );
+    }
+
+    #[test]
+    fn test_file_source() {
+        let path = std::env::temp_dir().join(format!("aoc-input-{}.txt", std::process::id()));
+        fs::write(&path, "1 2\n3 4\n").unwrap();
+        let source = InputSource::File(path.clone());
+
+        assert_eq!(source.read_to_string().unwrap(), "1 2\n3 4\n");
+        assert_eq!(source.lines().unwrap().count(), 2);
+        assert_eq!(source.chunks(8).unwrap().count(), 1);
+
+        fs::remove_file(&path).unwrap();
+        assert!(source.size().is_err());
+    }
+}
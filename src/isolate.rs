@@ -0,0 +1,381 @@
+//! Running each part of a day in a child process, so that a panic, running out of memory or
+//! hitting a resource limit fails only that part.
+//!
+//! The runner starts itself again with [`PART_COMMAND`], the day's year and name, and a part.
+//! The child applies the configured limits, runs the part and prints how long it took in
+//! seconds on one line, then the answer tagged with its kind by [`Answer::to_tagged`]. An error
+//! from the part is printed to stderr with exit code 1. The runner kills a child that takes longer than the wall-clock limit.
+
+use crate::{
+    answer::Answer,
+    config::Config,
+    solution::{timed, DayRun, PartRun},
+};
+use std::{
+    env,
+    io::{self, Read},
+    process::{Command, ExitStatus, Output, Stdio},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+pub const PART_COMMAND: &str = "__part";
+const ERROR_EXIT_CODE: i32 = 1;
+/// How often a child with a wall-clock limit is checked on.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Runs each of `parts` of the day or plugin called `name` in its own child process.
+pub fn run(config: &Config, year: u32, name: &str, parts: &[u32]) -> DayRun {
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let command = part_command(config, year, name, part);
+            let (output, elapsed) = timed(|| output_within(command, config.wall_limit));
+            let (answer, elapsed) = match output {
+                Ok(Some(output)) => {
+                    read_output(config, &output).unwrap_or_else(|e| (Err(e), elapsed))
+                }
+                Ok(None) => (
+                    Err(format!(
+                        "exceeded wall-clock limit of {} s",
+                        config.wall_limit.unwrap_or_default()
+                    )),
+                    elapsed,
+                ),
+                Err(e) => (Err(format!("Could not start child process: {e}")), elapsed),
+            };
+            PartRun {
+                part,
+                answer,
+                elapsed,
+            }
+        })
+        .collect();
+
+    DayRun {
+        phases: vec![],
+        parts,
+    }
+}
+
+fn part_command(config: &Config, year: u32, name: &str, part: u32) -> Command {
+    let exe = env::current_exe().unwrap_or_else(|_| env::args().next().unwrap().into());
+    let mut command = Command::new(exe);
+    command
+        .arg("--input-dir")
+        .arg(&config.input_dir)
+        .arg("--plugin-dir")
+        .arg(&config.plugin_dir);
+    if let Some(threads) = config.threads {
+        command.arg("--threads").arg(threads.to_string());
+    }
+    if let Some(memory_limit) = config.memory_limit {
+        command.arg("--memory-limit").arg(memory_limit.to_string());
+    }
+    if let Some(cpu_limit) = config.cpu_limit {
+        command.arg("--cpu-limit").arg(cpu_limit.to_string());
+    }
+    command.args([PART_COMMAND, &year.to_string(), name, &part.to_string()]);
+    command
+}
+
+/// Runs `command` to the end like [`Command::output`], or kills it after `limit` seconds and
+/// returns `None`.
+fn output_within(mut command: Command, limit: Option<u64>) -> io::Result<Option<Output>> {
+    let Some(limit) = limit else {
+        return command.output().map(Some);
+    };
+
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    // Read both pipes while waiting, so that a child filling one does not block.
+    let stdout = read_to_end(child.stdout.take());
+    let stderr = read_to_end(child.stderr.take());
+
+    let deadline = Instant::now() + Duration::from_secs(limit);
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            child.kill()?;
+            child.wait()?;
+            return Ok(None);
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    Ok(Some(Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    }))
+}
+
+fn read_to_end(pipe: Option<impl Read + Send + 'static>) -> JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut bytes = vec![];
+        if let Some(mut pipe) = pipe {
+            // A read error leaves what was read so far, as `Command::output` would fail anyway.
+            let _ = pipe.read_to_end(&mut bytes);
+        }
+        bytes
+    })
+}
+
+/// The answer and time a child printed, or why it failed. Output that cannot be read at all
+/// is an error.
+fn read_output(
+    config: &Config,
+    output: &Output,
+) -> Result<(Result<Answer, String>, Duration), String> {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        return Err(failure(config, output.status, &stderr));
+    }
+
+    let (seconds, answer) = stdout.split_once('\n').unwrap_or((&stdout, ""));
+    let elapsed = seconds
+        .parse()
+        .map(Duration::from_secs_f64)
+        .map_err(|_| format!("Unexpected output from child process: {stdout:?}"))?;
+    let answer = answer.strip_suffix('\n').unwrap_or(answer);
+    let answer = Answer::from_tagged(answer)
+        .ok_or_else(|| format!("Unexpected output from child process: {stdout:?}"))?;
+    Ok((Ok(answer), elapsed))
+}
+
+/// Says why a child process failed from how it exited and what it printed to stderr.
+fn failure(config: &Config, status: ExitStatus, stderr: &str) -> String {
+    if let Some(panic) = panic_message(stderr) {
+        return format!("panicked: {panic}");
+    }
+    if stderr.contains("memory allocation of") {
+        return match config.memory_limit {
+            Some(memory_limit) => format!("exceeded memory limit of {memory_limit} MiB"),
+            None => "ran out of memory".into(),
+        };
+    }
+    if status.code() == Some(ERROR_EXIT_CODE) {
+        return stderr.trim().into();
+    }
+
+    // Going over the soft CPU limit sends `SIGXCPU`, which ends the child. A `SIGKILL` can come
+    // from anywhere, so it is not taken for the CPU limit.
+    match signal(status) {
+        Some(signal) if Some(signal) == rlimit::SIGXCPU && config.cpu_limit.is_some() => format!(
+            "exceeded CPU limit of {} s",
+            config.cpu_limit.unwrap_or_default()
+        ),
+        Some(signal) => format!("killed by signal {signal}"),
+        None => format!("child process {status}"),
+    }
+}
+
+/// The message of the first panic in a child's stderr.
+fn panic_message(stderr: &str) -> Option<String> {
+    let mut lines = stderr.lines();
+    lines.find(|line| line.contains("panicked at"))?;
+    let message: Vec<&str> = lines
+        .take_while(|line| {
+            !line.starts_with("note: ")
+                && !line.starts_with("stack backtrace:")
+                && !line.contains("panicked at")
+        })
+        .collect();
+    Some(message.join("\n"))
+}
+
+#[cfg(unix)]
+fn signal(status: ExitStatus) -> Option<i32> {
+    std::os::unix::process::ExitStatusExt::signal(&status)
+}
+
+#[cfg(not(unix))]
+fn signal(_status: ExitStatus) -> Option<i32> {
+    None
+}
+
+/// Prints a part's result in the form [`read_output`] expects, exiting with an error if it
+/// failed.
+pub fn report_part(part_run: PartRun) -> ! {
+    match part_run.answer {
+        Ok(answer) => {
+            println!("{}\n{}", part_run.elapsed.as_secs_f64(), answer.to_tagged());
+            std::process::exit(0);
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            std::process::exit(ERROR_EXIT_CODE);
+        }
+    }
+}
+
+/// Limits the current process to `config`'s memory and CPU time.
+pub fn apply_limits(config: &Config) -> Result<(), String> {
+    if let Some(memory_limit) = config.memory_limit {
+        rlimit::set(rlimit::RLIMIT_AS, memory_limit.saturating_mul(1024 * 1024))
+            .map_err(|e| format!("Could not set memory limit: {e}"))?;
+    }
+    if let Some(cpu_limit) = config.cpu_limit {
+        rlimit::set(rlimit::RLIMIT_CPU, cpu_limit)
+            .map_err(|e| format!("Could not set CPU limit: {e}"))?;
+    }
+    Ok(())
+}
+
+// The numbers and the layout of `struct rlimit` below are those of these targets only. Others,
+// such as 32-bit or MIPS Linux, differ.
+#[cfg(all(
+    target_os = "linux",
+    target_pointer_width = "64",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+mod rlimit {
+    use std::{ffi::c_int, io};
+
+    pub const RLIMIT_CPU: c_int = 0;
+    pub const RLIMIT_AS: c_int = 9;
+    pub const SIGXCPU: Option<c_int> = Some(24);
+
+    #[repr(C)]
+    struct Rlimit {
+        current: u64,
+        max: u64,
+    }
+
+    extern "C" {
+        fn setrlimit(resource: c_int, rlim: *const Rlimit) -> c_int;
+    }
+
+    /// Sets the soft limit to `limit`. The hard limit is one more, so that going over the CPU
+    /// limit sends `SIGXCPU` before `SIGKILL`.
+    pub fn set(resource: c_int, limit: u64) -> io::Result<()> {
+        let rlimit = Rlimit {
+            current: limit,
+            max: limit.saturating_add(1),
+        };
+        match unsafe { setrlimit(resource, &rlimit) } {
+            0 => Ok(()),
+            _ => Err(io::Error::last_os_error()),
+        }
+    }
+}
+
+#[cfg(not(all(
+    target_os = "linux",
+    target_pointer_width = "64",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+)))]
+mod rlimit {
+    use std::{ffi::c_int, io};
+
+    pub const RLIMIT_CPU: c_int = 0;
+    pub const RLIMIT_AS: c_int = 0;
+    /// No CPU limit is set, so no signal is taken for going over it.
+    pub const SIGXCPU: Option<c_int> = None;
+
+    pub fn set(_resource: c_int, _limit: u64) -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "resource limits are only supported on 64-bit x86_64 and aarch64 Linux",
+        ))
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::process::ExitStatusExt;
+
+    #[test]
+    fn test_panic_message() {
+        let stderr = "thread 'main' panicked at src/advent/y2024/day6.rs:120:13:\n\
+                      Entered loop on part1\n\
+                      note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace\n";
+        assert_eq!(panic_message(stderr).unwrap(), "Entered loop on part1");
+        let stderr = "thread 'main' panicked at src/advent/y2024/day5.rs:80:9:\n\
+                      Could not find leaf node\n\
+                      stack backtrace:\n   0: rust_begin_unwind\n";
+        assert_eq!(panic_message(stderr).unwrap(), "Could not find leaf node");
+        assert_eq!(panic_message("memory allocation of 8 bytes failed\n"), None);
+    }
+
+    #[test]
+    fn test_failure() {
+        let config = Config {
+            memory_limit: Some(64),
+            cpu_limit: Some(5),
+            ..Config::default()
+        };
+        let abort = ExitStatus::from_raw(6);
+        assert_eq!(
+            failure(
+                &config,
+                abort,
+                "memory allocation of 1048576 bytes failed\n"
+            ),
+            "exceeded memory limit of 64 MiB"
+        );
+        if let Some(sigxcpu) = rlimit::SIGXCPU {
+            assert_eq!(
+                failure(&config, ExitStatus::from_raw(sigxcpu), ""),
+                "exceeded CPU limit of 5 s"
+            );
+        }
+        assert_eq!(
+            failure(
+                &config,
+                ExitStatus::from_raw(ERROR_EXIT_CODE << 8),
+                "bad input\n"
+            ),
+            "bad input"
+        );
+        assert_eq!(
+            failure(&config, ExitStatus::from_raw(9), ""),
+            "killed by signal 9"
+        );
+    }
+
+    #[test]
+    fn test_output_within() {
+        let mut echo = Command::new("echo");
+        echo.arg("hi");
+        let output = output_within(echo, Some(5)).unwrap().unwrap();
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"hi\n");
+
+        let mut sleep = Command::new("sleep");
+        sleep.arg("10");
+        let started = Instant::now();
+        assert!(output_within(sleep, Some(1)).unwrap().is_none());
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_read_output() {
+        let output = |stdout: &str| Output {
+            status: ExitStatus::from_raw(0),
+            stdout: stdout.as_bytes().to_vec(),
+            stderr: vec![],
+        };
+        let (answer, elapsed) = read_output(&Config::default(), &output("0.5\nint\n42\n")).unwrap();
+        assert_eq!(answer.unwrap(), 42);
+        assert_eq!(elapsed, Duration::from_millis(500));
+
+        let (answer, _) = read_output(&Config::default(), &output("0\nlines\nab\ncd\n")).unwrap();
+        assert!(answer.unwrap().is_multiline());
+
+        // Text that looks like a number comes back as text.
+        let stdout = format!("0\n{}\n", Answer::from("007").to_tagged());
+        let (answer, _) = read_output(&Config::default(), &output(&stdout)).unwrap();
+        assert_eq!(answer, Ok(Answer::from("007")));
+
+        assert!(read_output(&Config::default(), &output("")).is_err());
+        assert!(read_output(&Config::default(), &output("0\n42\n")).is_err());
+    }
+}
@@ -0,0 +1,32 @@
+//! The solutions and the tooling that runs, checks and measures them. The binary is the
+//! command line front end.
+
+pub mod advent;
+pub mod answer;
+pub mod bench;
+pub mod compare;
+pub mod config;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod fixtures;
+pub mod fuzz;
+pub mod graph;
+pub mod input;
+pub mod isolate;
+pub mod json;
+pub mod lint;
+pub mod minimize;
+pub mod plugin;
+pub mod repl;
+pub mod report;
+pub mod rng;
+pub mod scale;
+#[cfg(all(test, any(feature = "day6", feature = "day9")))]
+mod snapshot;
+pub mod solution;
+pub mod spec;
+pub mod test_report;
+mod toml;
+pub mod trace;
+pub mod verify;
+pub mod viz;
@@ -0,0 +1,189 @@
+//! Structural checks of puzzle inputs, run without solving, so that a malformed input is
+//! reported with where it goes wrong instead of found by a panic deep in a solver. Each day
+//! that has rules declares them as a `lint` function built from the checks here.
+
+use crate::json;
+use std::{fmt, str::FromStr};
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Violation {
+    /// Lines and columns count from 1. A violation without a line is about the whole input.
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+impl Violation {
+    pub fn input(message: impl Into<String>) -> Violation {
+        Violation {
+            line: None,
+            column: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn line(line: usize, message: impl Into<String>) -> Violation {
+        Violation {
+            line: Some(line),
+            column: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn at(line: usize, column: usize, message: impl Into<String>) -> Violation {
+        Violation {
+            line: Some(line),
+            column: Some(column),
+            message: message.into(),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        let number = |n: Option<usize>| n.map_or("null".into(), |n| n.to_string());
+        format!(
+            "{{\"line\": {}, \"column\": {}, \"message\": {}}}",
+            number(self.line),
+            number(self.column),
+            json::quote(&self.message)
+        )
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "line {line}, column {column}: ")?,
+            (Some(line), None) => write!(f, "line {line}: ")?,
+            _ => write!(f, "input: ")?,
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+/// The input's lines, numbered from 1.
+pub fn numbered_lines(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    input.lines().zip(1..).map(|(line, number)| (number, line))
+}
+
+pub fn non_empty(input: &str) -> Vec<Violation> {
+    match input.trim().is_empty() {
+        true => vec![Violation::input("is empty")],
+        false => vec![],
+    }
+}
+
+/// Every character that is not `allowed`, which is described as `expected`.
+pub fn chars(input: &str, allowed: fn(char) -> bool, expected: &str) -> Vec<Violation> {
+    numbered_lines(input)
+        .flat_map(|(number, line)| {
+            line.chars()
+                .zip(1..)
+                .filter(|(c, _)| !allowed(*c))
+                .map(move |(c, column)| {
+                    Violation::at(number, column, format!("expected {expected}, found {c:?}"))
+                })
+        })
+        .collect()
+}
+
+/// A non-empty grid of rows as wide as the first, made of `allowed` characters.
+pub fn grid(input: &str, allowed: fn(char) -> bool, expected: &str) -> Vec<Violation> {
+    let mut violations = non_empty(input);
+    if !violations.is_empty() {
+        return violations;
+    }
+
+    let width = input.lines().next().unwrap().chars().count();
+    for (number, line) in numbered_lines(input).skip(1) {
+        let line_width = line.chars().count();
+        if line_width != width {
+            violations.push(Violation::line(
+                number,
+                format!("row is {line_width} wide, but the first row is {width}"),
+            ));
+        }
+    }
+    violations.extend(chars(input, allowed, expected));
+    violations
+}
+
+/// Parses the `separator`-separated fields of line `number`, with a violation for each field
+/// that is not a `T`.
+pub fn numbers<T: FromStr>(
+    number: usize,
+    line: &str,
+    separator: Option<char>,
+) -> Result<Vec<T>, Vec<Violation>> {
+    let fields: Vec<&str> = match separator {
+        Some(separator) => line.split(separator).map(str::trim).collect(),
+        None => line.split_whitespace().collect(),
+    };
+
+    let mut values = vec![];
+    let mut violations = vec![];
+    for field in fields {
+        match field.parse() {
+            Ok(value) => values.push(value),
+            Err(_) => violations.push(Violation::line(
+                number,
+                format!("expected a number, found {field:?}"),
+            )),
+        }
+    }
+    match violations.is_empty() {
+        true => Ok(values),
+        false => Err(violations),
+    }
+}
+
+/// A check for each line, in order. Blank lines are checked too.
+pub fn each_line(input: &str, check: impl Fn(usize, &str) -> Vec<Violation>) -> Vec<Violation> {
+    numbered_lines(input)
+        .flat_map(|(number, line)| check(number, line))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid() {
+        let digit = |c: char| c.is_ascii_digit();
+        assert!(grid("123\n456\n", digit, "a digit").is_empty());
+        assert_eq!(
+            grid("123\n45\n7x9\n", digit, "a digit"),
+            vec![
+                Violation::line(2, "row is 2 wide, but the first row is 3"),
+                Violation::at(3, 2, "expected a digit, found 'x'"),
+            ]
+        );
+        assert_eq!(
+            grid("\n", digit, "a digit")[0].to_string(),
+            "input: is empty"
+        );
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(
+            numbers::<u32>(1, "75,47, 61", Some(',')),
+            Ok(vec![75, 47, 61])
+        );
+        let violations = numbers::<u32>(4, "1 x -2", None).unwrap_err();
+        assert_eq!(
+            violations
+                .iter()
+                .map(Violation::to_string)
+                .collect::<Vec<_>>(),
+            vec![
+                "line 4: expected a number, found \"x\"",
+                "line 4: expected a number, found \"-2\""
+            ]
+        );
+        assert_eq!(
+            violations[0].to_json(),
+            "{\"line\": 4, \"column\": null, \"message\": \"expected a number, found \\\"x\\\"\"}"
+        );
+    }
+}
@@ -1,28 +1,976 @@
-mod advent;
-
-use std::{env, process};
+use advent::Day;
+use advent_of_code_2024::{
+    advent, bench, compare, config, fixtures, fuzz, input, isolate, json, lint, minimize, plugin,
+    repl, report, rng, scale, solution, spec, test_report, verify, viz,
+};
+use bench::BenchResults;
+use config::{Config, Format};
+use input::InputSource;
+use plugin::{PluginDay, Plugins};
+use report::DayStatus;
+use solution::DayRun;
+use std::{
+    env, fs,
+    io::{self, BufWriter, Write},
+    path::Path,
+    process,
+    time::Duration,
+};
+use test_report::ReportFormat;
+use verify::{Answers, PartCheck, Verdict};
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        eprintln!("Usage: cargo run <exercise>");
+    let mut config = Config::load().unwrap_or_else(|e| exit_with_error(&e));
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args = config
+        .apply_flags(&args)
+        .unwrap_or_else(|e| exit_with_error(&e));
+
+    // Only the days that solve in parallel use rayon. Its global pool is set up before any of
+    // them starts it.
+    #[cfg(any(feature = "day6", feature = "day7"))]
+    if let Some(threads) = config.threads {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global()
+            .unwrap_or_else(|e| exit_with_error(&format!("Could not use {threads} threads: {e}")));
+    }
+    // Only the commands that resolve day names load plugins, so a broken plugin leaves the
+    // others working.
+    let plugins = || Plugins::load_dir(&config.plugin_dir).unwrap_or_else(|e| exit_with_error(&e));
+
+    match args.first().map(String::as_str) {
+        Some("all") if args.len() == 1 => all_command(&config, &plugins()),
+        Some("bench") => bench_command(&config, &plugins(), &args[1..]),
+        Some("compare") => compare_command(&config, &args[1..]),
+        Some("config") => config_command(&config, &args[1..]),
+        Some("fixtures") => fixtures_command(&config, &args[1..]),
+        Some("fuzz") => fuzz_command(&config, &args[1..]),
+        Some(isolate::PART_COMMAND) => part_command(&config, &plugins(), &args[1..]),
+        Some("lint") => lint_command(&config, &args[1..]),
+        Some("minimize") => minimize_command(&config, &args[1..]),
+        Some("repl") if args.len() == 1 => repl_command(&config),
+        Some("report") if args.len() == 1 => report_command(&config),
+        Some("scale") => scale_command(&config, &args[1..]),
+        Some("verify") => verify_command(&config, &plugins(), &args[1..]),
+        Some("viz") => viz_command(&config, &args[1..]),
+        Some(exercise) => match plugins().find(exercise) {
+            Some(plugin_day) if args.len() == 1 => {
+                let day_run = run_plugin_day(&config, plugin_day, &spec::PARTS);
+                print_results(&config, &plugin_day.name, &day_run, false);
+            }
+            _ => run_command(&config, &args),
+        },
+        _ => usage(),
+    }
+}
+
+fn usage() -> ! {
+    eprintln!("Usage: cargo run [options] <spec>... [--explain]");
+    eprintln!("       cargo run [options] <plugin day>");
+    eprintln!("       cargo run [options] all");
+    eprintln!("       cargo run [options] verify [spec...] [--report <junit|tap>]");
+    eprintln!("       cargo run [options] fixtures [spec...] [--report <junit|tap>]");
+    eprintln!("       cargo run [options] bench [spec...] [--runs <n>]");
+    eprintln!("       cargo run [options] compare [spec...] [--runs <n>]");
+    eprintln!("       cargo run [options] lint [spec...] [--input <file>]");
+    eprintln!("       cargo run [options] fuzz [spec...] [--runs <n>] [--seed <n>]");
+    eprintln!(
+        "       cargo run [options] minimize <spec> --until <panic|disagree|differs> [--oracle <command>] [--input <file>] [--output <file>]"
+    );
+    eprintln!("       cargo run [options] repl");
+    eprintln!("       cargo run [options] report");
+    eprintln!(
+        "       cargo run [options] scale [spec...] [--min-size <n>] [--steps <n>] [--seed <n>] [--budget <seconds>] [--csv <file>]"
+    );
+    eprintln!("       cargo run [options] viz <spec> [--cast <file>]");
+    eprintln!("       cargo run [options] config show");
+    eprintln!();
+    eprintln!("Specs select days and parts: 6, day6:2, 1..5, 1,3,7:1, all:2, 2024:6:2");
+    eprintln!();
+    eprintln!(
+        "Options (defaults come from aoc.toml or ${}):",
+        config::CONFIG_ENV_VAR
+    );
+    eprintln!("  --input-dir <dir>  --plugin-dir <dir>  --threads <n>");
+    eprintln!("  --format <text|json>  --speed <fps>");
+    eprintln!("  --isolate [true|false]  --memory-limit <MiB>  --cpu-limit <seconds>");
+    eprintln!("  --wall-limit <seconds>  --time-limit <seconds>  --bench-file <file>");
+    process::exit(1);
+}
+
+fn exit_with_error(message: &str) -> ! {
+    eprintln!("{message}");
+    process::exit(1);
+}
+
+/// Removes `flag` and its value from `args`, returning the value if the flag was given.
+fn take_flag(args: &[String], flag: &str) -> (Vec<String>, Option<String>) {
+    match args.iter().position(|arg| arg == flag) {
+        Some(i) => {
+            let Some(value) = args.get(i + 1) else {
+                exit_with_error(&format!("Missing value for {flag}"));
+            };
+            let rest = [&args[..i], &args[i + 2..]].concat();
+            (rest, Some(value.clone()))
+        }
+        None => (args.to_vec(), None),
+    }
+}
+
+fn take_report_flag(args: &[String]) -> (Vec<String>, Option<ReportFormat>) {
+    let (rest, report) = take_flag(args, "--report");
+    let report = report.map(|report| {
+        report
+            .parse()
+            .unwrap_or_else(|e: String| exit_with_error(&e))
+    });
+    (rest, report)
+}
+
+/// The days and parts selected by the run specs in `args`, or every part of every day if
+/// there are none.
+fn select_targets(args: &[String]) -> Vec<(&'static Day, Vec<u32>)> {
+    if args.iter().any(|arg| arg.starts_with("--")) {
+        usage();
+    }
+
+    let days: Vec<(u32, u32)> = advent::days().map(|day| (day.year, day.number())).collect();
+    let targets = spec::parse(args, &days).unwrap_or_else(|e| exit_with_error(&e));
+    targets
+        .into_iter()
+        .map(|target| {
+            let day = advent::find_day(target.year, target.day).unwrap();
+            (day, target.parts)
+        })
+        .collect()
+}
+
+/// The plugin days that solve one of `targets`, with the same parts. Every plugin day is
+/// selected if there are no `specs`, including those for days that are not built in.
+fn select_plugin_days<'a>(
+    plugins: &'a Plugins,
+    specs: &[String],
+    targets: &[(&Day, Vec<u32>)],
+) -> Vec<(&'a PluginDay, Vec<u32>)> {
+    plugins
+        .days()
+        .iter()
+        .filter_map(|plugin_day| {
+            if specs.is_empty() {
+                return Some((plugin_day, spec::PARTS.to_vec()));
+            }
+            targets
+                .iter()
+                .find(|(day, _)| day.year == plugin_day.year && day.number() == plugin_day.day)
+                .map(|(_, parts)| (plugin_day, parts.clone()))
+        })
+        .collect()
+}
+
+fn load_answers(config: &Config, year: u32) -> Answers {
+    let answers_path = config
+        .input_dir
+        .join(year.to_string())
+        .join(verify::ANSWERS_FILE);
+    Answers::load(&answers_path).unwrap_or_else(|e| exit_with_error(&e))
+}
+
+fn load_bench_results(config: &Config) -> BenchResults {
+    BenchResults::load(&config.bench_file).unwrap_or_else(|e| exit_with_error(&e))
+}
+
+fn verify_day(config: &Config, day: &Day, parts: &[u32]) -> Vec<PartCheck> {
+    let answers = load_answers(config, day.year);
+    let day_run = run_day(config, day, parts);
+    verify::check(day.year, day.name, day.name, day_run, &answers)
+}
+
+fn verify_plugin_day(config: &Config, plugin_day: &PluginDay, parts: &[u32]) -> Vec<PartCheck> {
+    let answers = load_answers(config, plugin_day.year);
+    let day_run = run_plugin_day(config, plugin_day, parts);
+    let input_name = plugin_day.input_name();
+    verify::check(
+        plugin_day.year,
+        &plugin_day.name,
+        &input_name,
+        day_run,
+        &answers,
+    )
+}
+
+/// Puzzle inputs are kept as `<input_dir>/<year>/<input_name>.txt`.
+fn input_source(config: &Config, year: u32, input_name: &str) -> InputSource {
+    let year_dir = config.input_dir.join(year.to_string());
+    InputSource::File(year_dir.join(format!("{input_name}.txt")))
+}
+
+fn read_input(config: &Config, year: u32, input_name: &str) -> String {
+    let source = input_source(config, year, input_name);
+    source
+        .read_to_string()
+        .unwrap_or_else(|e| exit_with_error(&format!("Could not read {source}: {e}")))
+}
+
+/// Runs `parts` of `day` on its puzzle input, each in a child process if `config.isolate` is
+/// set. Inputs over `input::STREAM_THRESHOLD` are streamed if the day can stream them, rather
+/// than read into memory whole.
+fn run_day(config: &Config, day: &Day, parts: &[u32]) -> DayRun {
+    if config.isolate {
+        return isolate::run(config, day.year, day.name, parts);
+    }
+    let source = input_source(config, day.year, day.name);
+    if let Some(stream) = day.stream {
+        if source.size().is_ok_and(|len| len > input::STREAM_THRESHOLD) {
+            return stream(&source, parts);
+        }
+    }
+    (day.run)(&read_input(config, day.year, day.name), parts)
+}
+
+fn run_plugin_day(config: &Config, plugin_day: &PluginDay, parts: &[u32]) -> DayRun {
+    if config.isolate {
+        return isolate::run(config, plugin_day.year, &plugin_day.name, parts);
+    }
+    let input = read_input(config, plugin_day.year, &plugin_day.input_name());
+    plugin_day.run(&input, parts)
+}
+
+/// Runs one part in the child process started by `isolate::run`, within the configured limits.
+fn part_command(config: &Config, plugins: &Plugins, args: &[String]) {
+    let [year, name, part] = args else {
+        usage();
+    };
+    let (Ok(year), Ok(part)) = (year.parse(), part.parse()) else {
+        usage();
+    };
+    isolate::apply_limits(config).unwrap_or_else(|e| exit_with_error(&e));
+
+    let config = Config {
+        isolate: false,
+        ..config.clone()
+    };
+    let day_run = match advent::days().find(|day| day.year == year && day.name == name) {
+        Some(day) => run_day(&config, day, &[part]),
+        None => match plugins.find(name) {
+            Some(plugin_day) => run_plugin_day(&config, plugin_day, &[part]),
+            None => exit_with_error(&format!("Unknown day: {name}")),
+        },
+    };
+    match day_run.parts.into_iter().next() {
+        Some(part_run) => isolate::report_part(part_run),
+        None => exit_with_error(&format!("{year}/{name} has no part {part}")),
+    }
+}
+
+fn print_results(config: &Config, name: &str, day_run: &DayRun, timings: bool) {
+    match config.format {
+        Format::Text => {
+            if timings {
+                println!("{name}");
+            }
+            for part_run in &day_run.parts {
+                let part = part_run.part;
+                match &part_run.answer {
+                    Ok(answer) if answer.is_multiline() => println!("Part {part}:\n{answer}"),
+                    Ok(answer) => println!("Part {part}: {answer}"),
+                    Err(e) => println!("Part {part} failed: {e}"),
+                }
+            }
+            if timings {
+                let phases = day_run
+                    .phases
+                    .iter()
+                    .map(|(name, elapsed)| (name.to_string(), elapsed));
+                let parts = day_run
+                    .parts
+                    .iter()
+                    .map(|part_run| (format!("part {}", part_run.part), &part_run.elapsed));
+                let timings: Vec<String> = phases
+                    .chain(parts)
+                    .map(|(name, elapsed)| format!("{name} {elapsed:.2?}"))
+                    .collect();
+                println!("  ({})", timings.join(", "));
+            }
+        }
+        Format::Json => {
+            let parts: Vec<String> = day_run
+                .parts
+                .iter()
+                .map(|part_run| {
+                    let result = match &part_run.answer {
+                        Ok(answer) => format!("\"answer\": {}", answer.to_json()),
+                        Err(e) => format!("\"error\": {}", json::quote(e)),
+                    };
+                    let seconds = match timings {
+                        true => format!(", \"seconds\": {}", part_run.elapsed.as_secs_f64()),
+                        false => String::new(),
+                    };
+                    format!("{{\"part\": {}, {result}{seconds}}}", part_run.part)
+                })
+                .collect();
+            let phases = match timings {
+                true => {
+                    let phases: Vec<String> = day_run
+                        .phases
+                        .iter()
+                        .map(|(name, elapsed)| {
+                            format!("{}: {}", json::quote(name), elapsed.as_secs_f64())
+                        })
+                        .collect();
+                    format!(", \"phases\": {{{}}}", phases.join(", "))
+                }
+                false => String::new(),
+            };
+            println!(
+                "{{\"day\": {}, \"parts\": [{}]{phases}}}",
+                json::quote(name),
+                parts.join(", ")
+            );
+        }
+    }
+}
+
+fn all_command(config: &Config, plugins: &Plugins) {
+    for day in advent::days() {
+        let day_run = run_day(config, day, &spec::PARTS);
+        print_results(config, &day.id(), &day_run, true);
+    }
+
+    for plugin_day in plugins.days() {
+        let day_run = run_plugin_day(config, plugin_day, &spec::PARTS);
+        print_results(config, &plugin_day.name, &day_run, true);
+    }
+}
+
+fn config_command(config: &Config, args: &[String]) {
+    if args != ["show"] {
+        usage();
+    }
+
+    match config.format {
+        Format::Text => print!("{config}"),
+        Format::Json => println!("{}", config.to_json()),
+    }
+}
+
+fn run_command(config: &Config, args: &[String]) {
+    let explain = args.iter().any(|arg| arg == "--explain");
+    let specs: Vec<String> = args
+        .iter()
+        .filter(|arg| *arg != "--explain")
+        .cloned()
+        .collect();
+    let targets = select_targets(&specs);
+    // Several days are shown like `all`, with a heading and timings for each.
+    let many_days = targets.len() > 1;
+
+    for (day, parts) in targets {
+        if !explain {
+            print_results(config, &day.id(), &run_day(config, day, &parts), many_days);
+            continue;
+        }
+
+        let Some(explain) = day.explain else {
+            exit_with_error(&format!("No explanation for {}", day.id()));
+        };
+        let mut trace = explain(&read_input(config, day.year, day.name));
+        trace.retain_parts(&parts);
+        match config.format {
+            Format::Text if many_days => print!("{}\n{trace}", day.id()),
+            Format::Text => print!("{trace}"),
+            Format::Json => println!("{}", trace.to_json()),
+        }
+    }
+}
+
+fn viz_command(config: &Config, args: &[String]) {
+    let (specs, cast_path) = take_flag(args, "--cast");
+    if specs.is_empty() {
+        usage();
+    }
+    let day = match select_targets(&specs)[..] {
+        [(day, _)] => day,
+        _ => exit_with_error("viz shows one day at a time"),
+    };
+    let Some(frames) = day.frames else {
+        exit_with_error(&format!("No visualization for {}", day.id()));
+    };
+
+    let frames = frames(&read_input(config, day.year, day.name));
+
+    let result = match cast_path {
+        Some(cast_path) => fs::File::create(cast_path).and_then(|file| {
+            let mut out = BufWriter::new(file);
+            viz::write_asciicast(&day.id(), frames, config.speed, &mut out)?;
+            out.flush()
+        }),
+        None => viz::play(frames, config.speed),
+    };
+
+    if let Err(e) = result {
+        exit_with_error(&format!("Visualization failed: {e}"));
+    }
+}
+
+fn verify_command(config: &Config, plugins: &Plugins, args: &[String]) {
+    let (args, report) = take_report_flag(args);
+    let targets = select_targets(&args);
+    let plugin_targets = select_plugin_days(plugins, &args, &targets);
+
+    let checks = targets
+        .into_iter()
+        .flat_map(|(day, parts)| verify_day(config, day, &parts))
+        .chain(
+            plugin_targets
+                .into_iter()
+                .flat_map(|(plugin_day, parts)| verify_plugin_day(config, plugin_day, &parts)),
+        );
+    finish_checks(config, "verify", checks, report);
+}
+
+fn fixtures_command(config: &Config, args: &[String]) {
+    let (args, report) = take_report_flag(args);
+    let fixtures_dir = Path::new(fixtures::FIXTURES_DIR);
+
+    let checks = select_targets(&args).into_iter().flat_map(|(day, parts)| {
+        let (day_fixtures, answers) = fixtures::load(fixtures_dir, day.year, day.name)
+            .unwrap_or_else(|e| exit_with_error(&e));
+        day_fixtures
+            .into_iter()
+            .flat_map(move |fixture| {
+                let day_run = (day.run)(&fixture.input, &parts);
+                verify::check(day.year, day.name, &fixture.name, day_run, &answers)
+            })
+            .collect::<Vec<_>>()
+    });
+
+    finish_checks(config, "fixtures", checks, report);
+}
+
+/// Prints each check as it completes, or a report once all are done, and exits with an error
+/// if any failed.
+fn finish_checks(
+    config: &Config,
+    suite: &str,
+    checks: impl Iterator<Item = PartCheck>,
+    report: Option<ReportFormat>,
+) {
+    let mut finished = vec![];
+    for check in checks {
+        if report.is_none() {
+            match config.format {
+                Format::Text => println!("{check}"),
+                Format::Json => println!("{}", check.to_json()),
+            }
+        }
+        finished.push(check);
+    }
+
+    if let Some(report) = report {
+        print!("{}", test_report::render(report, suite, &finished));
+    }
+
+    if finished
+        .iter()
+        .any(|check| check.verdict() == Verdict::Fail)
+    {
+        process::exit(1);
+    }
+}
+
+fn bench_command(config: &Config, plugins: &Plugins, args: &[String]) {
+    let (specs, runs) = take_flag(args, "--runs");
+    let runs = match runs {
+        Some(runs) => match runs.parse() {
+            Ok(runs) if runs > 0 => runs,
+            _ => exit_with_error(&format!("Invalid runs: {runs}")),
+        },
+        None => bench::DEFAULT_RUNS,
+    };
+    let bench_path = &config.bench_file;
+    let mut results = load_bench_results(config);
+    let targets = select_targets(&specs);
+    let plugin_targets = select_plugin_days(plugins, &specs, &targets);
+
+    let mut bench_day = |year: u32, name: &str, id: &str, run: &dyn Fn() -> DayRun| {
+        for (part, median) in bench::bench(run, runs) {
+            results.set(year, name, part, median);
+            match config.format {
+                Format::Text => println!("{id} part {part}: median {median:.2?} over {runs} runs"),
+                Format::Json => println!(
+                    "{{\"year\": {year}, \"day\": {}, \"part\": {part}, \"median_seconds\": {}, \"runs\": {runs}}}",
+                    json::quote(name),
+                    median.as_secs_f64()
+                ),
+            }
+        }
+        // Saved after every day so that an interrupted bench keeps what it measured.
+        results
+            .save(bench_path)
+            .unwrap_or_else(|e| exit_with_error(&e));
+    };
+
+    for (day, parts) in targets {
+        let input = read_input(config, day.year, day.name);
+        bench_day(day.year, day.name, &day.id(), &|| (day.run)(&input, &parts));
+    }
+    for (plugin_day, parts) in plugin_targets {
+        let input = read_input(config, plugin_day.year, &plugin_day.input_name());
+        let run = || plugin_day.run(&input, &parts);
+        bench_day(plugin_day.year, &plugin_day.name, &plugin_day.name, &run);
+    }
+}
+
+fn parse_flag<T: std::str::FromStr>(name: &str, value: Option<String>, default: T) -> T {
+    match value {
+        Some(value) => value
+            .parse()
+            .unwrap_or_else(|_| exit_with_error(&format!("Invalid {name}: {value}"))),
+        None => default,
+    }
+}
+
+/// Runs every variant of the selected parts on each day's puzzle input, exiting with an error
+/// after printing the comparisons if any variant disagrees with the main solution.
+fn compare_command(config: &Config, args: &[String]) {
+    let (specs, runs) = take_flag(args, "--runs");
+    let runs = parse_flag("runs", runs, compare::DEFAULT_RUNS);
+    if runs == 0 {
+        exit_with_error("compare needs at least 1 run");
+    }
+
+    let targets: Vec<_> = select_targets(&specs)
+        .into_iter()
+        .filter(|(day, _)| {
+            if day.compare.is_none() {
+                eprintln!("{}: no variants, skipping", day.id());
+            }
+            day.compare.is_some()
+        })
+        .collect();
+    if targets.is_empty() {
+        exit_with_error("None of the selected days have variants");
+    }
+
+    let mut disagreements = vec![];
+    for (day, parts) in targets {
+        let run_variants = day.compare.unwrap();
+        let input = read_input(config, day.year, day.name);
+        let comparisons = compare::compare(|| run_variants(&input, &parts), runs);
+
+        match config.format {
+            Format::Text => print!("{}\n{}", day.id(), compare::table(&comparisons)),
+            Format::Json => {
+                let parts: Vec<String> = comparisons
+                    .iter()
+                    .map(|comparison| {
+                        let variants: Vec<String> = comparison
+                            .variants
+                            .iter()
+                            .map(|variant| {
+                                let result = match &variant.answer {
+                                    Ok(answer) => format!("\"answer\": {}", answer.to_json()),
+                                    Err(e) => format!("\"error\": {}", json::quote(&e.to_string())),
+                                };
+                                format!(
+                                    "{{\"name\": {}, {result}, \"median_seconds\": {}}}",
+                                    json::quote(variant.name),
+                                    variant.median.as_secs_f64()
+                                )
+                            })
+                            .collect();
+                        format!(
+                            "{{\"part\": {}, \"agree\": {}, \"variants\": [{}]}}",
+                            comparison.part,
+                            comparison.disagreements().is_empty(),
+                            variants.join(", ")
+                        )
+                    })
+                    .collect();
+                println!(
+                    "{{\"year\": {}, \"day\": {}, \"parts\": [{}]}}",
+                    day.year,
+                    json::quote(day.name),
+                    parts.join(", ")
+                );
+            }
+        }
+
+        for comparison in &comparisons {
+            for variant in comparison.disagreements() {
+                disagreements.push(format!(
+                    "{} part {}: {} gave {}, {} gave {}",
+                    day.id(),
+                    comparison.part,
+                    comparison.variants[0].name,
+                    solution::format_result(&comparison.variants[0].answer),
+                    variant.name,
+                    solution::format_result(&variant.answer)
+                ));
+            }
+        }
+    }
+
+    if !disagreements.is_empty() {
+        exit_with_error(&disagreements.join("\n"));
+    }
+}
+
+fn scale_command(config: &Config, args: &[String]) {
+    let (args, csv_path) = take_flag(args, "--csv");
+    let (args, min_size) = take_flag(&args, "--min-size");
+    let (args, steps) = take_flag(&args, "--steps");
+    let (args, seed) = take_flag(&args, "--seed");
+    let (specs, budget) = take_flag(&args, "--budget");
+    let min_size = parse_flag("min size", min_size, scale::DEFAULT_MIN_SIZE);
+    let steps = parse_flag("steps", steps, scale::DEFAULT_STEPS);
+    let seed = parse_flag("seed", seed, scale::DEFAULT_SEED);
+    let budget = match budget {
+        Some(budget) => budget
+            .parse()
+            .ok()
+            .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+            .unwrap_or_else(|| exit_with_error(&format!("Invalid budget: {budget}"))),
+        None => scale::DEFAULT_BUDGET,
+    };
+    if min_size == 0 || steps < 2 {
+        exit_with_error("scale needs a min size of at least 1 and at least 2 steps");
+    }
+
+    let sizes = scale::sizes(min_size, steps);
+    let targets: Vec<_> = select_targets(&specs)
+        .into_iter()
+        .filter(|(day, _)| {
+            if day.generate.is_none() {
+                eprintln!("{}: no input generator, skipping", day.id());
+            }
+            day.generate.is_some()
+        })
+        .collect();
+    if targets.is_empty() {
+        exit_with_error("None of the selected days can generate inputs");
+    }
+
+    let mut csv = String::from(scale::CSV_HEADER);
+    for (day, parts) in targets {
+        let generate = day.generate.unwrap();
+        let scaling = scale::measure(
+            |size| generate(size, &mut rng::Rng::new(seed)),
+            |input| (day.run)(input, &parts),
+            &sizes,
+            budget,
+        );
+        if scaling.sizes.len() < sizes.len() {
+            eprintln!(
+                "{}: stopped after size {}, which took over {budget:.2?}",
+                day.id(),
+                scaling.sizes.last().unwrap()
+            );
+        }
+
+        match config.format {
+            Format::Text => print!("{}\n{}", day.id(), scale::table(&scaling)),
+            Format::Json => {
+                let parts: Vec<String> = scaling
+                    .parts
+                    .iter()
+                    .map(|(part, times)| {
+                        let seconds: Vec<String> = times
+                            .iter()
+                            .map(|time| time.as_secs_f64().to_string())
+                            .collect();
+                        let exponent = scale::growth_exponent(&scaling.sizes, times)
+                            .map_or("null".into(), |exponent| exponent.to_string());
+                        format!(
+                            "{{\"part\": {part}, \"seconds\": [{}], \"exponent\": {exponent}}}",
+                            seconds.join(", ")
+                        )
+                    })
+                    .collect();
+                let sizes: Vec<String> = scaling.sizes.iter().map(usize::to_string).collect();
+                println!(
+                    "{{\"year\": {}, \"day\": {}, \"sizes\": [{}], \"parts\": [{}]}}",
+                    day.year,
+                    json::quote(day.name),
+                    sizes.join(", "),
+                    parts.join(", ")
+                );
+            }
+        }
+        csv += &scale::csv_rows(day.year, day.name, &scaling);
+    }
+
+    if let Some(csv_path) = csv_path {
+        fs::write(&csv_path, csv)
+            .unwrap_or_else(|e| exit_with_error(&format!("Could not write {csv_path}: {e}")));
+    }
+}
+
+/// Checks the structure of each selected day's puzzle input, or of `--input` for one day,
+/// without solving it. Exits with an error if there are any violations.
+fn lint_command(config: &Config, args: &[String]) {
+    let (specs, input_path) = take_flag(args, "--input");
+    let targets: Vec<_> = select_targets(&specs)
+        .into_iter()
+        .map(|(day, _)| day)
+        .filter(|day| {
+            if day.lint.is_none() {
+                eprintln!("{}: no lint rules, skipping", day.id());
+            }
+            day.lint.is_some()
+        })
+        .collect();
+    if targets.is_empty() {
+        exit_with_error("None of the selected days have lint rules");
+    }
+    if input_path.is_some() && targets.len() > 1 {
+        exit_with_error("--input needs a spec selecting one day");
+    }
+
+    let mut failed = false;
+    for day in targets {
+        let source = match &input_path {
+            Some(path) => InputSource::File(path.into()),
+            None => input_source(config, day.year, day.name),
+        };
+        let input = source
+            .read_to_string()
+            .unwrap_or_else(|e| exit_with_error(&format!("Could not read {source}: {e}")));
+        let violations = (day.lint.unwrap())(&input);
+        failed |= !violations.is_empty();
+
+        match config.format {
+            Format::Text if violations.is_empty() => println!("{} ({source}): ok", day.id()),
+            Format::Text => {
+                let plural = if violations.len() == 1 { "" } else { "s" };
+                println!(
+                    "{} ({source}): {} violation{plural}",
+                    day.id(),
+                    violations.len()
+                );
+                for violation in &violations {
+                    println!("  {violation}");
+                }
+            }
+            Format::Json => {
+                let violations: Vec<String> =
+                    violations.iter().map(lint::Violation::to_json).collect();
+                println!(
+                    "{{\"year\": {}, \"day\": {}, \"input\": {}, \"violations\": [{}]}}",
+                    day.year,
+                    json::quote(day.name),
+                    json::quote(&source.to_string()),
+                    violations.join(", ")
+                );
+            }
+        }
+    }
+
+    if failed {
+        process::exit(1);
+    }
+}
+
+/// Fuzzes each selected day with mutants of its fixtures, saving a shrunk input for each crash
+/// under `fuzz/crashes`. Exits with an error if there are any crashes.
+fn fuzz_command(config: &Config, args: &[String]) {
+    let (args, runs) = take_flag(args, "--runs");
+    let (specs, seed) = take_flag(&args, "--seed");
+    let runs = parse_flag("runs", runs, fuzz::DEFAULT_RUNS);
+    let seed = parse_flag("seed", seed, fuzz::DEFAULT_SEED);
+    let time_limit = config.time_limit;
+
+    // Panics are the point, so they are recorded instead of printed.
+    fuzz::set_panic_hook();
+    let mut failed = false;
+    for (day, parts) in select_targets(&specs) {
+        let (fixtures, _answers) =
+            fixtures::load(Path::new(fixtures::FIXTURES_DIR), day.year, day.name)
+                .unwrap_or_else(|e| exit_with_error(&e));
+        if fixtures.is_empty() {
+            eprintln!("{}: no fixtures to mutate, skipping", day.id());
+            continue;
+        }
+
+        let seeds: Vec<String> = fixtures.into_iter().map(|fixture| fixture.input).collect();
+        let mut rng = rng::Rng::new(seed);
+        let mut checker = fuzz::Checker::new(day.run, &parts, time_limit);
+        let crashes = fuzz::fuzz(&mut checker, day.shape, &seeds, runs, &mut rng);
+        let crash_dir = Path::new(fuzz::CRASHES_DIR).join(day.id());
+        let paths: Vec<String> = crashes
+            .iter()
+            .map(|crash| {
+                fuzz::save(crash, &crash_dir)
+                    .map(|path| path.display().to_string())
+                    .unwrap_or_else(|e| {
+                        exit_with_error(&format!("Could not save to {}: {e}", crash_dir.display()))
+                    })
+            })
+            .collect();
+        failed |= !crashes.is_empty();
+
+        match config.format {
+            Format::Text => {
+                let plural = if crashes.len() == 1 { "" } else { "es" };
+                println!(
+                    "{}: {runs} inputs, {} crash{plural}",
+                    day.id(),
+                    crashes.len()
+                );
+                for (crash, path) in crashes.iter().zip(&paths) {
+                    println!("  {} ({path})", crash.failure);
+                }
+            }
+            Format::Json => {
+                let crashes: Vec<String> = crashes
+                    .iter()
+                    .zip(&paths)
+                    .map(|(crash, path)| {
+                        format!(
+                            "{{\"failure\": {}, \"file\": {}}}",
+                            json::quote(&crash.failure.to_string()),
+                            json::quote(path)
+                        )
+                    })
+                    .collect();
+                println!(
+                    "{{\"year\": {}, \"day\": {}, \"runs\": {runs}, \"crashes\": [{}]}}",
+                    day.year,
+                    json::quote(day.name),
+                    crashes.join(", ")
+                );
+            }
+        }
+
+        // The hung thread cannot be stopped, and would slow every day after it.
+        if checker.hung.is_some() {
+            eprintln!("{}: an input timed out, stopping", day.id());
+            break;
+        }
+    }
+
+    if failed {
         process::exit(1);
     }
+}
 
-    match args[1].as_str() {
-        "day1" => advent::day1::run(),
-        "day2" => advent::day2::run(),
-        "day3" => advent::day3::run(),
-        "day4" => advent::day4::run(),
-        "day5" => advent::day5::run(),
-        "day6" => advent::day6::run(),
-        "day7" => advent::day7::run(),
-        "day8" => advent::day8::run(),
-        "day9" => advent::day9::run(),
-        "day10" => advent::day10::run(),
-        _ => {
-            eprintln!("Unknown exercise: {}", args[1]);
-            std::process::exit(1);
+/// Shrinks the input of one day, or `--input`, to a small one for which `--until` still holds:
+/// the day panics in the same place, its ways of solving a part disagree, or it differs from
+/// what the `--oracle` command prints.
+fn minimize_command(config: &Config, args: &[String]) {
+    let (args, until) = take_flag(args, "--until");
+    let (args, oracle) = take_flag(&args, "--oracle");
+    let (args, input_path) = take_flag(&args, "--input");
+    let (specs, output_path) = take_flag(&args, "--output");
+    let targets = select_targets(&specs);
+    let [(day, parts)] = targets.as_slice() else {
+        exit_with_error("minimize needs a spec selecting one day");
+    };
+    let source = match &input_path {
+        Some(path) => InputSource::File(path.into()),
+        None => input_source(config, day.year, day.name),
+    };
+    let input = source
+        .read_to_string()
+        .unwrap_or_else(|e| exit_with_error(&format!("Could not read {source}: {e}")));
+
+    let time_limit = config.time_limit;
+    let mut fails: Box<dyn FnMut(&str) -> bool> = match until.as_deref() {
+        Some("panic") => {
+            fuzz::set_panic_hook();
+            let mut checker = fuzz::Checker::new(day.run, parts, time_limit);
+            let Err(failure @ fuzz::Failure::Panic { .. }) = checker.check(&input) else {
+                exit_with_error(&format!("{} does not panic on {source}", day.id()));
+            };
+            eprintln!("{}: {failure}", day.id());
+            // After a timeout every check fails differently, so shrinking stops there.
+            Box::new(move |candidate| {
+                matches!(checker.check(candidate),
+                    Err(f) if f.signature() == failure.signature())
+            })
+        }
+        Some("disagree") => {
+            if day.stream.is_none() && day.compare.is_none() {
+                exit_with_error(&format!("{} has only one way of solving it", day.id()));
+            }
+            Box::new(|candidate| minimize::disagrees(day, candidate, parts))
         }
+        Some("differs") => {
+            let Some(oracle) = oracle else {
+                exit_with_error("--until differs needs an --oracle command");
+            };
+            let oracle: Vec<String> = oracle.split_whitespace().map(String::from).collect();
+            Box::new(move |candidate| minimize::differs(day, candidate, parts, &oracle))
+        }
+        _ => usage(),
+    };
+    if !fails(&input) {
+        exit_with_error(&format!("--until does not hold for {source}"));
+    }
+
+    let minimized = minimize::minimize(&input, day.shape, &mut fails);
+    eprintln!(
+        "{}: shrunk from {} lines ({} bytes) to {} lines ({} bytes)",
+        day.id(),
+        input.lines().count(),
+        input.len(),
+        minimized.lines().count(),
+        minimized.len()
+    );
+    match output_path {
+        Some(path) => fs::write(&path, &minimized)
+            .unwrap_or_else(|e| exit_with_error(&format!("Could not write {path}: {e}"))),
+        None => print!("{minimized}"),
+    }
+}
+
+fn repl_command(config: &Config) {
+    // Panics in the session are caught and reported as failed parts, so they are silenced
+    // until it ends.
+    let previous_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| {}));
+    let mut session = repl::Session::new(
+        config.input_dir.clone(),
+        Path::new(fixtures::FIXTURES_DIR).into(),
+    );
+    let stdin = io::stdin();
+    let interactive = io::IsTerminal::is_terminal(&stdin);
+    if interactive {
+        println!("Type help for the commands.");
     }
+    let result = repl::run(&mut session, stdin.lock(), io::stdout(), interactive);
+    std::panic::set_hook(previous_hook);
+    result.unwrap_or_else(|e| exit_with_error(&format!("Could not read commands: {e}")));
+}
+
+fn report_command(config: &Config) {
+    let bench_results = load_bench_results(config);
+
+    let statuses: Vec<DayStatus> = advent::days()
+        .map(|day| DayStatus {
+            year: day.year,
+            name: day.name,
+            title: day.title,
+            source: format!("src/advent/y{}/{}.rs", day.year, day.name),
+            parts: verify_day(config, day, &spec::PARTS)
+                .iter()
+                .map(|check| {
+                    let median = bench_results.get(day.year, day.name, check.part);
+                    (check.part, check.verdict(), median)
+                })
+                .collect(),
+        })
+        .collect();
+
+    let readme = match fs::read_to_string(report::README_PATH) {
+        Ok(readme) => Some(readme),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+        Err(e) => exit_with_error(&format!("Could not read {}: {e}", report::README_PATH)),
+    };
+    let readme = report::update_readme(readme.as_deref(), &report::table(&statuses));
+    fs::write(report::README_PATH, readme).unwrap_or_else(|e| {
+        exit_with_error(&format!("Could not write {}: {e}", report::README_PATH))
+    });
+    println!("Wrote {}", report::README_PATH);
 }
@@ -0,0 +1,310 @@
+//! Shrinking a failing input to a small one that still fails, by delta debugging. What counts
+//! as failing is a predicate on the input, such as [`disagrees`] or [`differs`]. Each day's
+//! input is shrunk in the units its format is made of, so that a grid stays rectangular and
+//! day 5 keeps its rules and updates apart.
+
+use crate::{
+    advent::Day,
+    answer::Answer,
+    input::InputSource,
+    solution::{catch_panic, DayRun},
+};
+use std::{
+    io::Write,
+    ops::Range,
+    process::{Command, Stdio},
+};
+
+/// How a day's input is laid out, which decides how it can be cut down.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shape {
+    /// One record per line, which loses whole lines.
+    Lines,
+    /// A grid of characters, which loses whole rows and columns.
+    Grid,
+    /// Blocks of lines separated by blank lines, which lose lines but keep the blocks.
+    Sections,
+    /// Free text, which loses single characters.
+    Text,
+}
+
+/// Shrinks `input` while it still `fails`, in the units of `shape`. `fails` must hold for
+/// `input` itself.
+pub fn minimize(input: &str, shape: Shape, mut fails: impl FnMut(&str) -> bool) -> String {
+    let end = if input.ends_with('\n') { "\n" } else { "" };
+    let join = |lines: &[&str]| match lines {
+        [] => String::new(),
+        _ => lines.join("\n") + end,
+    };
+
+    match shape {
+        Shape::Lines => {
+            let lines = ddmin(input.lines().collect(), |lines| fails(&join(lines)));
+            join(&lines)
+        }
+        Shape::Grid => {
+            let rows = ddmin(input.lines().collect(), |rows| fails(&join(rows)));
+            let width = rows
+                .iter()
+                .map(|row| row.chars().count())
+                .max()
+                .unwrap_or(0);
+            let columns_of = |columns: &[usize]| -> String {
+                let rows: Vec<String> = rows
+                    .iter()
+                    .map(|row| {
+                        row.chars()
+                            .enumerate()
+                            .filter(|(i, _)| columns.contains(i))
+                            .map(|(_, c)| c)
+                            .collect()
+                    })
+                    .collect();
+                join(&rows.iter().map(String::as_str).collect::<Vec<_>>())
+            };
+            let columns = ddmin((0..width).collect(), |columns| fails(&columns_of(columns)));
+            columns_of(&columns)
+        }
+        Shape::Sections => {
+            let join_sections = |sections: &[Vec<&str>]| {
+                let sections: Vec<String> = sections.iter().map(|lines| lines.join("\n")).collect();
+                sections.join("\n\n") + end
+            };
+            let mut sections: Vec<Vec<&str>> = input
+                .trim_end_matches('\n')
+                .split("\n\n")
+                .map(|section| section.lines().collect())
+                .collect();
+            for i in 0..sections.len() {
+                let lines = ddmin(sections[i].clone(), |lines| {
+                    let mut candidate = sections.clone();
+                    candidate[i] = lines.to_vec();
+                    fails(&join_sections(&candidate))
+                });
+                sections[i] = lines;
+            }
+            join_sections(&sections)
+        }
+        Shape::Text => {
+            let chars = ddmin(input.chars().collect(), |chars| {
+                fails(&chars.iter().collect::<String>())
+            });
+            chars.into_iter().collect()
+        }
+    }
+}
+
+/// Zeller's delta debugging: a subsequence of `items` for which `test` holds and from which no
+/// single item can be removed, found by trying ever smaller chunks and their complements.
+/// `test` must hold for `items` itself.
+pub fn ddmin<T: Clone>(mut items: Vec<T>, mut test: impl FnMut(&[T]) -> bool) -> Vec<T> {
+    if test(&[]) {
+        return vec![];
+    }
+
+    let mut chunk_count = 2;
+    while items.len() >= 2 {
+        let chunk_len = items.len().div_ceil(chunk_count);
+        let chunks: Vec<Range<usize>> = (0..items.len())
+            .step_by(chunk_len)
+            .map(|start| start..(start + chunk_len).min(items.len()))
+            .collect();
+
+        if let Some(chunk) = chunks.iter().find(|chunk| test(&items[(*chunk).clone()])) {
+            items = items[chunk.clone()].to_vec();
+            chunk_count = 2;
+            continue;
+        }
+        // With two chunks each one is the other's complement, which was just tried.
+        let complement =
+            |chunk: &Range<usize>| [&items[..chunk.start], &items[chunk.end..]].concat();
+        if chunks.len() > 2 {
+            if let Some(complement) = chunks.iter().map(complement).find(|items| test(items)) {
+                items = complement;
+                chunk_count = (chunk_count - 1).max(2);
+                continue;
+            }
+        }
+
+        if chunk_count >= items.len() {
+            break;
+        }
+        chunk_count = (chunk_count * 2).min(items.len());
+    }
+    items
+}
+
+/// The answers of `parts` of `day_run`, with each error as its message.
+fn answers(day_run: DayRun) -> Vec<(u32, Result<Answer, String>)> {
+    day_run
+        .parts
+        .into_iter()
+        .map(|part_run| (part_run.part, part_run.answer))
+        .collect()
+}
+
+/// Whether the day's ways of solving one of `parts`, its variants and its stream solver, give
+/// different answers or errors for `input`. An input that any of them panics on does not
+/// count, so that shrinking keeps to inputs they all accept.
+pub fn disagrees(day: &Day, input: &str, parts: &[u32]) -> bool {
+    let results = catch_panic(|| {
+        let mut results = answers((day.run)(input, parts));
+        if let Some(stream) = day.stream {
+            results.extend(answers(stream(&InputSource::Text(input.into()), parts)));
+        }
+        if let Some(run_variants) = day.compare {
+            results.extend(
+                run_variants(input, parts)
+                    .into_iter()
+                    .map(|run| (run.part, run.answer.map_err(|e| e.to_string()))),
+            );
+        }
+        results
+    });
+    let Ok(results) = results else {
+        return false;
+    };
+
+    parts.iter().any(|part| {
+        let mut answers = results.iter().filter(|(p, _)| p == part).map(|(_, a)| a);
+        answers
+            .next()
+            .is_some_and(|first| answers.any(|answer| answer != first))
+    })
+}
+
+/// Whether the day's answer to one of `parts` of `input` is not what `oracle` expects. The
+/// oracle is a command that is given the part as its last argument and the input on stdin,
+/// and prints the expected answer. An input that the oracle fails on or the day panics on
+/// does not count.
+pub fn differs(day: &Day, input: &str, parts: &[u32], oracle: &[String]) -> bool {
+    let Ok(day_run) = catch_panic(|| (day.run)(input, parts)) else {
+        return false;
+    };
+
+    let mut differs = false;
+    for (part, answer) in answers(day_run) {
+        let Some(expected) = expected_answer(oracle, input, part) else {
+            return false;
+        };
+        differs |= match answer {
+            Ok(answer) => answer.to_string() != expected,
+            Err(_) => true,
+        };
+    }
+    differs
+}
+
+/// The first line the oracle prints, or `None` if it could not be run or failed.
+fn expected_answer(oracle: &[String], input: &str, part: u32) -> Option<String> {
+    let (program, args) = oracle.split_first()?;
+    let mut child = Command::new(program)
+        .args(args)
+        .arg(part.to_string())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    // An oracle that stops reading early is judged by its output all the same.
+    let _ = child.stdin.take()?.write_all(input.as_bytes());
+    let output = child.wait_with_output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Some(stdout.lines().next().unwrap_or_default().trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "day1")]
+    use crate::{advent, solution::VariantRun};
+    #[cfg(feature = "day1")]
+    use std::time::Duration;
+
+    #[test]
+    fn test_ddmin() {
+        let mut tests = 0;
+        let items = ddmin((0..100).collect(), |items| {
+            tests += 1;
+            items.contains(&13) && items.contains(&71)
+        });
+        assert_eq!(items, vec![13, 71]);
+        assert!(tests < 100);
+        assert_eq!(ddmin(vec![1, 2, 3], |_| true), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn test_minimize() {
+        let has = |needles: &'static [&str]| {
+            move |input: &str| needles.iter().all(|needle| input.contains(needle))
+        };
+        assert_eq!(
+            minimize("1 2\n3 4\n5 6\n", Shape::Lines, has(&["3"])),
+            "3 4\n"
+        );
+        assert_eq!(
+            minimize(".#.\n...\n^..\n", Shape::Grid, has(&["#", "^"])),
+            ".#\n^.\n"
+        );
+        assert_eq!(
+            minimize(
+                "1|2\n3|4\n\n1,2\n3,4",
+                Shape::Sections,
+                has(&["3|4", "\n\n", "3,4"])
+            ),
+            "3|4\n\n3,4"
+        );
+        assert_eq!(
+            minimize("xmul(2,4)&mul[3,7]", Shape::Text, has(&["mul(", ")"])),
+            "mul()"
+        );
+    }
+
+    #[cfg(feature = "day1")]
+    fn zero_variant(_input: &str, parts: &[u32]) -> Vec<VariantRun> {
+        parts
+            .iter()
+            .map(|&part| VariantRun {
+                part,
+                name: "zero",
+                answer: Ok(0.into()),
+                elapsed: Duration::ZERO,
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "day1")]
+    #[test]
+    fn test_disagrees() {
+        let day1 = advent::find_day(2024, 1).unwrap();
+        assert!(!disagrees(day1, "3   4\n4   3\n", &[1, 2]));
+        assert!(!disagrees(day1, "3   x\n", &[1, 2]));
+
+        // A variant that always answers 0 disagrees wherever the distance is not 0.
+        let day = Day {
+            compare: Some(zero_variant),
+            ..*day1
+        };
+        let input = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+        assert!(disagrees(&day, input, &[1]));
+        let shrunk = minimize(input, Shape::Lines, |input| disagrees(&day, input, &[1]));
+        assert_eq!(shrunk.lines().count(), 1);
+        assert!(disagrees(&day, &shrunk, &[1]));
+    }
+
+    #[cfg(feature = "day1")]
+    #[test]
+    fn test_differs() {
+        let day1 = advent::find_day(2024, 1).unwrap();
+        let oracle: Vec<String> = ["sh", "-c", "echo 11"].map(String::from).to_vec();
+        let input = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+        assert!(!differs(day1, input, &[1], &oracle));
+        assert!(differs(day1, "3   5\n", &[1], &oracle));
+
+        let failing: Vec<String> = ["sh", "-c", "exit 1"].map(String::from).to_vec();
+        assert!(!differs(day1, "3   5\n", &[1], &failing));
+    }
+}
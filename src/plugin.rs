@@ -0,0 +1,368 @@
+//! Solver plugins loaded from `cdylib` libraries at startup.
+//!
+//! A plugin exports `aoc_plugin_init`, which the runner calls with [`ABI_VERSION`] and a
+//! register callback. The plugin calls the callback once per solver it provides, passing the
+//! year, day and part it solves, a variant name and a [`SolveFn`]. `aoc_plugin_init` returns 0
+//! on success and anything else if it does not support the ABI version. A plugin that
+//! registers a solver for a year without puzzles here is rejected.
+//!
+//! A solver receives the puzzle input as UTF-8 bytes and writes its answer into `out`, as a line
+//! naming its kind, `int`, `text` or `lines`, then the answer, so that text such as `007` is not
+//! read as a number. It returns the length in bytes, which may be more than `out_cap` to ask
+//! for a bigger buffer, or a negative number if it failed.
+
+use crate::{
+    advent,
+    answer::Answer,
+    solution::{timed, DayRun, PartRun},
+};
+use std::{
+    ffi::{c_char, c_void, CStr},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+pub const ABI_VERSION: u32 = 3;
+const INIT_SYMBOL: &CStr = c"aoc_plugin_init";
+
+pub type SolveFn =
+    unsafe extern "C" fn(input: *const u8, input_len: usize, out: *mut u8, out_cap: usize) -> isize;
+pub type RegisterFn = unsafe extern "C" fn(
+    registrar: *mut c_void,
+    year: u32,
+    day: u32,
+    part: u32,
+    name: *const c_char,
+    solve: SolveFn,
+);
+type InitFn =
+    unsafe extern "C" fn(abi_version: u32, registrar: *mut c_void, register: RegisterFn) -> i32;
+
+pub struct PluginDay {
+    pub name: String,
+    pub year: u32,
+    pub day: u32,
+    solvers: Vec<(u32, SolveFn)>,
+}
+
+#[derive(Default)]
+pub struct Plugins {
+    days: Vec<PluginDay>,
+    libraries: Vec<Library>,
+}
+
+impl PluginDay {
+    pub fn input_name(&self) -> String {
+        format!("day{}", self.day)
+    }
+
+    pub fn run(&self, input: &str, parts: &[u32]) -> DayRun {
+        let parts = self
+            .solvers
+            .iter()
+            .filter(|(part, _solve)| parts.contains(part))
+            .map(|(part, solve)| {
+                let (answer, elapsed) = timed(|| call_solver(*solve, input));
+                PartRun {
+                    part: *part,
+                    answer: answer.and_then(|answer| {
+                        Answer::from_tagged(&answer)
+                            .ok_or_else(|| format!("solver wrote an answer of no kind: {answer:?}"))
+                    }),
+                    elapsed,
+                }
+            })
+            .collect();
+
+        DayRun {
+            phases: vec![],
+            parts,
+        }
+    }
+}
+
+impl Plugins {
+    /// Loads every shared library in `plugin_dir`. A missing directory means no plugins.
+    pub fn load_dir(plugin_dir: &Path) -> Result<Plugins, String> {
+        let mut plugins = Plugins::default();
+
+        let entries = match fs::read_dir(plugin_dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(plugins),
+            Err(e) => return Err(format!("Could not read {}: {e}", plugin_dir.display())),
+        };
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension() == Some(std::env::consts::DLL_EXTENSION.as_ref()))
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            plugins.load(&path)?;
+        }
+
+        Ok(plugins)
+    }
+
+    fn load(&mut self, path: &Path) -> Result<(), String> {
+        let library = Library::open(path)?;
+        let init: InitFn = unsafe { std::mem::transmute(library.symbol(INIT_SYMBOL)?) };
+        let days = unsafe { init_days(init) }.map_err(|e| format!("{}: {e}", path.display()))?;
+
+        self.days.extend(days);
+        self.libraries.push(library);
+        Ok(())
+    }
+
+    pub fn days(&self) -> &[PluginDay] {
+        &self.days
+    }
+
+    pub fn find(&self, name: &str) -> Option<&PluginDay> {
+        self.days.iter().find(|day| day.name == name)
+    }
+}
+
+/// Calls a plugin's `aoc_plugin_init` and returns the days it registered.
+unsafe fn init_days(init: InitFn) -> Result<Vec<PluginDay>, String> {
+    let mut days: Vec<PluginDay> = vec![];
+    let status = init(ABI_VERSION, &mut days as *mut _ as *mut c_void, register);
+    if status != 0 {
+        return Err(format!("does not support plugin ABI version {ABI_VERSION}"));
+    }
+
+    if let Some(plugin_day) = days
+        .iter()
+        .find(|plugin_day| !advent::YEAR_NUMBERS.contains(&plugin_day.year))
+    {
+        return Err(format!(
+            "{} is for {}, which has no puzzles",
+            plugin_day.name, plugin_day.year
+        ));
+    }
+
+    Ok(days)
+}
+
+/// The register callback handed to plugins. `registrar` is the `Vec<PluginDay>` being filled.
+unsafe extern "C" fn register(
+    registrar: *mut c_void,
+    year: u32,
+    day: u32,
+    part: u32,
+    name: *const c_char,
+    solve: SolveFn,
+) {
+    let days = &mut *(registrar as *mut Vec<PluginDay>);
+    let name = format!("day{day}@{}", CStr::from_ptr(name).to_string_lossy());
+
+    match days
+        .iter_mut()
+        .find(|plugin_day| plugin_day.year == year && plugin_day.name == name)
+    {
+        Some(plugin_day) => {
+            plugin_day.solvers.push((part, solve));
+            plugin_day.solvers.sort_by_key(|(part, _solve)| *part);
+        }
+        None => days.push(PluginDay {
+            name,
+            year,
+            day,
+            solvers: vec![(part, solve)],
+        }),
+    }
+}
+
+fn call_solver(solve: SolveFn, input: &str) -> Result<String, String> {
+    let mut out = vec![0u8; 64];
+
+    loop {
+        let len = unsafe { solve(input.as_ptr(), input.len(), out.as_mut_ptr(), out.len()) };
+        let len = usize::try_from(len).map_err(|_| format!("solver returned {len}"))?;
+        if len <= out.len() {
+            out.truncate(len);
+            return String::from_utf8(out).map_err(|e| e.to_string());
+        }
+        out.resize(len, 0);
+    }
+}
+
+struct Library {
+    handle: *mut c_void,
+}
+
+#[cfg(unix)]
+mod dl {
+    use std::ffi::{c_char, c_int, c_void};
+
+    pub const RTLD_NOW: c_int = 2;
+
+    #[cfg_attr(target_os = "linux", link(name = "dl"))]
+    extern "C" {
+        pub fn dlopen(filename: *const c_char, flag: c_int) -> *mut c_void;
+        pub fn dlsym(handle: *mut c_void, symbol: *const c_char) -> *mut c_void;
+        pub fn dlclose(handle: *mut c_void) -> c_int;
+        pub fn dlerror() -> *mut c_char;
+    }
+
+    pub fn last_error() -> String {
+        let error = unsafe { dlerror() };
+        if error.is_null() {
+            "unknown error".into()
+        } else {
+            unsafe { std::ffi::CStr::from_ptr(error) }
+                .to_string_lossy()
+                .into_owned()
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Library {
+    fn open(path: &Path) -> Result<Library, String> {
+        use std::os::unix::ffi::OsStrExt;
+
+        let path_c = std::ffi::CString::new(path.as_os_str().as_bytes())
+            .map_err(|e| format!("Invalid plugin path {}: {e}", path.display()))?;
+        let handle = unsafe { dl::dlopen(path_c.as_ptr(), dl::RTLD_NOW) };
+        if handle.is_null() {
+            return Err(format!(
+                "Could not load {}: {}",
+                path.display(),
+                dl::last_error()
+            ));
+        }
+        Ok(Library { handle })
+    }
+
+    fn symbol(&self, name: &CStr) -> Result<*mut c_void, String> {
+        let symbol = unsafe { dl::dlsym(self.handle, name.as_ptr()) };
+        if symbol.is_null() {
+            return Err(format!("Missing symbol {name:?}: {}", dl::last_error()));
+        }
+        Ok(symbol)
+    }
+}
+
+#[cfg(unix)]
+impl Drop for Library {
+    fn drop(&mut self) {
+        unsafe { dl::dlclose(self.handle) };
+    }
+}
+
+#[cfg(not(unix))]
+impl Library {
+    fn open(path: &Path) -> Result<Library, String> {
+        Err(format!(
+            "Could not load {}: plugins are only supported on unix",
+            path.display()
+        ))
+    }
+
+    fn symbol(&self, _name: &CStr) -> Result<*mut c_void, String> {
+        unreachable!()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    unsafe extern "C" fn echo(
+        input: *const u8,
+        input_len: usize,
+        out: *mut u8,
+        out_cap: usize,
+    ) -> isize {
+        if input_len <= out_cap {
+            std::ptr::copy_nonoverlapping(input, out, input_len);
+        }
+        input_len as isize
+    }
+
+    unsafe extern "C" fn fail(_: *const u8, _: usize, _: *mut u8, _: usize) -> isize {
+        -1
+    }
+
+    unsafe extern "C" fn test_init(
+        abi_version: u32,
+        registrar: *mut c_void,
+        register: RegisterFn,
+    ) -> i32 {
+        if abi_version != ABI_VERSION {
+            return 1;
+        }
+        register(registrar, 2024, 3, 2, c"echo".as_ptr(), fail);
+        register(registrar, 2024, 3, 1, c"echo".as_ptr(), echo);
+        0
+    }
+
+    unsafe extern "C" fn future_init(
+        _abi_version: u32,
+        registrar: *mut c_void,
+        register: RegisterFn,
+    ) -> i32 {
+        register(registrar, 2099, 1, 1, c"echo".as_ptr(), echo);
+        0
+    }
+
+    unsafe extern "C" fn old_init(_abi_version: u32, _: *mut c_void, _: RegisterFn) -> i32 {
+        1
+    }
+
+    #[test]
+    fn test_register() {
+        let days = unsafe { init_days(test_init) }.unwrap();
+
+        assert_eq!(days.len(), 1);
+        assert_eq!(days[0].name, "day3@echo");
+        assert_eq!(days[0].year, 2024);
+        assert_eq!(days[0].input_name(), "day3");
+        let parts: Vec<u32> = days[0].solvers.iter().map(|(part, _)| *part).collect();
+        assert_eq!(parts, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_init_errors() {
+        assert_eq!(
+            unsafe { init_days(future_init) }.err().unwrap(),
+            "day1@echo is for 2099, which has no puzzles"
+        );
+        assert_eq!(
+            unsafe { init_days(old_init) }.err().unwrap(),
+            "does not support plugin ABI version 3"
+        );
+    }
+
+    #[test]
+    fn test_call_solver() {
+        assert_eq!(call_solver(echo, "short").unwrap(), "short");
+        let long_input = "long ".repeat(1000);
+        assert_eq!(call_solver(echo, &long_input).unwrap(), long_input);
+        assert_eq!(call_solver(fail, "").unwrap_err(), "solver returned -1");
+    }
+
+    #[test]
+    fn test_run_keeps_answer_kind() {
+        let plugin_day = PluginDay {
+            name: "day3@echo".into(),
+            year: 2024,
+            day: 3,
+            solvers: vec![(1, echo)],
+        };
+        let answer = |input: &str| plugin_day.run(input, &[1]).parts.remove(0).answer;
+        assert_eq!(answer("text\n007"), Ok(Answer::from("007")));
+        assert_eq!(answer("int\n7"), Ok(Answer::from(7)));
+        assert_eq!(
+            answer("007"),
+            Err("solver wrote an answer of no kind: \"007\"".into())
+        );
+    }
+
+    #[test]
+    fn test_load_dir_missing() {
+        let plugins = Plugins::load_dir(Path::new("does/not/exist")).unwrap();
+        assert!(plugins.days().is_empty());
+    }
+}
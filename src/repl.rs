@@ -0,0 +1,519 @@
+//! An interactive session for trying out inputs: pick a day, paste or edit an input buffer
+//! and run parts, explain traces or renders on it. Each run is kept in the session's history,
+//! and the buffer can be saved as a fixture.
+
+use crate::{
+    advent::{self, Day},
+    answer::Answer,
+    fixtures, json,
+    solution::catch_panic,
+    spec,
+    verify::ANSWERS_FILE,
+};
+use std::{
+    fs,
+    io::{self, BufRead, Write},
+    path::PathBuf,
+};
+
+pub const PROMPT: &str = "aoc> ";
+/// A line with only this on it ends a paste.
+pub const END_PASTE: &str = ".";
+
+pub const HELP: &str = "\
+Commands:
+  day <spec>          choose a day, e.g. 7 or 2024:7
+  load [fixture]      load the day's puzzle input, or one of its fixtures
+  paste               read lines into the buffer until a line with just \".\"
+  show                print the buffer with line numbers
+  set <n> <text>      replace line n
+  insert <n> <text>   insert a line before line n
+  append <text>       add a line at the end
+  delete <n>          delete line n
+  clear               empty the buffer
+  run [part]          run both parts, or one, on the buffer
+  explain             trace the parts on the buffer
+  render [frame]      print the last frame of the day's rendering, or frame n
+  history             list the runs of this session
+  restore <n>         put the day and input of run n back in the buffer
+  save <name>         save the buffer as a fixture, with the answers of its latest run
+  help                print this
+  quit                leave the session
+";
+
+/// A run kept in the session's history.
+pub struct Run {
+    pub day: &'static Day,
+    pub input: String,
+    pub answers: Vec<(u32, Result<Answer, String>)>,
+}
+
+pub struct Session {
+    input_dir: PathBuf,
+    fixtures_dir: PathBuf,
+    day: Option<&'static Day>,
+    buffer: Vec<String>,
+    /// The lines read so far while pasting.
+    paste: Option<Vec<String>>,
+    pub history: Vec<Run>,
+}
+
+/// What the session says in response to a line, and whether to go on.
+pub enum Response {
+    Continue(String),
+    Quit,
+}
+
+impl Session {
+    pub fn new(input_dir: PathBuf, fixtures_dir: PathBuf) -> Session {
+        Session {
+            input_dir,
+            fixtures_dir,
+            day: None,
+            buffer: vec![],
+            paste: None,
+            history: vec![],
+        }
+    }
+
+    pub fn is_pasting(&self) -> bool {
+        self.paste.is_some()
+    }
+
+    pub fn input(&self) -> String {
+        self.buffer.iter().map(|line| format!("{line}\n")).collect()
+    }
+
+    pub fn handle(&mut self, line: &str) -> Response {
+        if let Some(paste) = &mut self.paste {
+            if line.trim_end() != END_PASTE {
+                paste.push(line.to_string());
+                return Response::Continue(String::new());
+            }
+            self.buffer = self.paste.take().unwrap();
+            return Response::Continue(format!("{} lines in the buffer\n", self.buffer.len()));
+        }
+
+        let line = line.trim();
+        let (command, rest) = line.split_once(' ').unwrap_or((line, ""));
+        let rest = rest.trim();
+        let result = match command {
+            "" => Ok(String::new()),
+            "quit" | "exit" => return Response::Quit,
+            "help" => Ok(HELP.into()),
+            "day" => self.choose_day(rest),
+            "load" => self.load(rest),
+            "paste" => {
+                self.paste = Some(vec![]);
+                Ok(format!(
+                    "Paste the input, then a line with just {END_PASTE:?}\n"
+                ))
+            }
+            "show" => Ok(self.show()),
+            "set" | "insert" | "append" | "delete" => self.edit(command, rest),
+            "clear" => {
+                self.buffer.clear();
+                Ok(String::new())
+            }
+            "run" => self.run(rest),
+            "explain" => self.explain(),
+            "render" => self.render(rest),
+            "history" => Ok(self.list_history()),
+            "restore" => self.restore(rest),
+            "save" => self.save(rest),
+            _ => Err(format!("Unknown command: {command}; try help")),
+        };
+
+        Response::Continue(result.unwrap_or_else(|e| format!("{e}\n")))
+    }
+
+    fn current_day(&self) -> Result<&'static Day, String> {
+        self.day
+            .ok_or_else(|| "Choose a day first, e.g. day 6".into())
+    }
+
+    fn choose_day(&mut self, spec: &str) -> Result<String, String> {
+        let days: Vec<(u32, u32)> = advent::days().map(|day| (day.year, day.number())).collect();
+        let targets = spec::parse(&[spec.to_string()], &days)?;
+        let [target] = targets.as_slice() else {
+            return Err(format!("{spec:?} selects more than one day"));
+        };
+        let day = advent::find_day(target.year, target.day).unwrap();
+        self.day = Some(day);
+        Ok(format!("{}: {}\n", day.id(), day.title))
+    }
+
+    fn load(&mut self, fixture: &str) -> Result<String, String> {
+        let day = self.current_day()?;
+        let input = match fixture {
+            "" => {
+                let path = self
+                    .input_dir
+                    .join(day.year.to_string())
+                    .join(format!("{}.txt", day.name));
+                fs::read_to_string(&path)
+                    .map_err(|e| format!("Could not read {}: {e}", path.display()))?
+            }
+            name => {
+                let (fixtures, _) = fixtures::load(&self.fixtures_dir, day.year, day.name)?;
+                let fixture = fixtures
+                    .into_iter()
+                    .find(|fixture| fixture.name == name)
+                    .ok_or_else(|| format!("{} has no fixture {name}", day.id()))?;
+                fixture.input
+            }
+        };
+        self.buffer = input.lines().map(String::from).collect();
+        Ok(format!("{} lines in the buffer\n", self.buffer.len()))
+    }
+
+    fn show(&self) -> String {
+        let width = self.buffer.len().to_string().len();
+        self.buffer
+            .iter()
+            .enumerate()
+            .map(|(i, line)| format!("{:>width$} | {line}\n", i + 1))
+            .collect()
+    }
+
+    /// Applies `set`, `insert`, `append` or `delete` to the buffer. Lines are numbered from 1.
+    fn edit(&mut self, command: &str, args: &str) -> Result<String, String> {
+        if command == "append" {
+            self.buffer.push(args.to_string());
+            return Ok(String::new());
+        }
+
+        let (n, text) = args.split_once(' ').unwrap_or((args, ""));
+        let n: usize = n
+            .parse()
+            .map_err(|_| format!("Invalid line number: {n:?}"))?;
+        let last = match command {
+            "insert" => self.buffer.len() + 1,
+            _ => self.buffer.len(),
+        };
+        if n == 0 || n > last {
+            return Err(format!(
+                "No line {n}; the buffer has {} lines",
+                self.buffer.len()
+            ));
+        }
+
+        match command {
+            "set" => self.buffer[n - 1] = text.to_string(),
+            "insert" => self.buffer.insert(n - 1, text.to_string()),
+            _ => {
+                self.buffer.remove(n - 1);
+            }
+        }
+        Ok(String::new())
+    }
+
+    fn run(&mut self, part: &str) -> Result<String, String> {
+        let day = self.current_day()?;
+        let parts = match part {
+            "" => spec::PARTS.to_vec(),
+            part => match part.parse() {
+                Ok(part) if spec::PARTS.contains(&part) => vec![part],
+                _ => return Err(format!("Invalid part: {part}")),
+            },
+        };
+
+        let input = self.input();
+        let answers = match catch_panic(|| (day.run)(&input, &parts)) {
+            Ok(day_run) => day_run
+                .parts
+                .into_iter()
+                .map(|part_run| (part_run.part, part_run.answer))
+                .collect(),
+            Err(e) => parts
+                .iter()
+                .map(|&part| (part, Err(format!("panicked: {e}"))))
+                .collect(),
+        };
+
+        let run = Run {
+            day,
+            input,
+            answers,
+        };
+        let output = format_answers(&run.answers, "\n");
+        self.history.push(run);
+        Ok(format!("{output}\n"))
+    }
+
+    fn explain(&self) -> Result<String, String> {
+        let day = self.current_day()?;
+        let explain = day
+            .explain
+            .ok_or_else(|| format!("{} has no explain trace", day.id()))?;
+        let input = self.input();
+        let trace = catch_panic(|| explain(&input)).map_err(|e| format!("panicked: {e}"))?;
+        Ok(trace.to_string())
+    }
+
+    fn render(&self, frame: &str) -> Result<String, String> {
+        let day = self.current_day()?;
+        let frames = day
+            .frames
+            .ok_or_else(|| format!("{} has no rendering", day.id()))?;
+        let input = self.input();
+
+        // Frames are made one at a time, so only the one shown is kept. They are only counted,
+        // which walks them all again, when there is no such frame.
+        let index = match frame {
+            "" => None,
+            n => {
+                let n: usize = n.parse().map_err(|_| format!("Invalid frame: {n:?}"))?;
+                Some(n.checked_sub(1))
+            }
+        };
+        let frame = catch_panic(|| match index {
+            None => frames(&input).last(),
+            Some(Some(i)) => frames(&input).nth(i),
+            Some(None) => None,
+        })
+        .map_err(|e| format!("panicked: {e}"))?;
+
+        match frame {
+            Some(frame) => Ok(format!("{frame}\n")),
+            None => {
+                let count =
+                    catch_panic(|| frames(&input).count()).map_err(|e| format!("panicked: {e}"))?;
+                Err(format!("There are {count} frames"))
+            }
+        }
+    }
+
+    fn list_history(&self) -> String {
+        self.history
+            .iter()
+            .enumerate()
+            .map(|(i, run)| {
+                format!(
+                    "{:>3}  {}  {} lines  {}\n",
+                    i + 1,
+                    run.day.id(),
+                    run.input.lines().count(),
+                    format_answers(&run.answers, ", ")
+                )
+            })
+            .collect()
+    }
+
+    fn restore(&mut self, n: &str) -> Result<String, String> {
+        let run = n
+            .parse::<usize>()
+            .ok()
+            .and_then(|n| n.checked_sub(1))
+            .and_then(|i| self.history.get(i))
+            .ok_or_else(|| format!("No run {n:?}; see history"))?;
+        self.day = Some(run.day);
+        self.buffer = run.input.lines().map(String::from).collect();
+        Ok(format!(
+            "{}, {} lines in the buffer\n",
+            run.day.id(),
+            self.buffer.len()
+        ))
+    }
+
+    /// Writes the buffer to the day's fixture directory, and records the answers of the latest
+    /// run on exactly this buffer in its `answers.toml`. Existing fixtures are not replaced.
+    fn save(&self, name: &str) -> Result<String, String> {
+        let day = self.current_day()?;
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(format!(
+                "Invalid fixture name: {name:?}; use letters, digits and _"
+            ));
+        }
+
+        let day_dir = self.fixtures_dir.join(day.year.to_string()).join(day.name);
+        let path = day_dir.join(format!("{name}.txt"));
+        if path.exists() {
+            return Err(format!("{} already exists", path.display()));
+        }
+        fs::create_dir_all(&day_dir)
+            .map_err(|e| format!("Could not create {}: {e}", day_dir.display()))?;
+        let input = self.input();
+        fs::write(&path, &input).map_err(|e| format!("Could not write {}: {e}", path.display()))?;
+
+        let latest = self
+            .history
+            .iter()
+            .rev()
+            .find(|run| run.day.id() == day.id() && run.input == input);
+        let answers: Vec<(u32, &Answer)> = latest
+            .iter()
+            .flat_map(|run| &run.answers)
+            .filter_map(|(part, answer)| Some((*part, answer.as_ref().ok()?)))
+            .collect();
+        if answers.is_empty() {
+            return Ok(format!(
+                "Saved {} without answers; run it first to record them\n",
+                path.display()
+            ));
+        }
+
+        let answers_path = day_dir.join(ANSWERS_FILE);
+        let mut answers_file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&answers_path)
+            .map_err(|e| format!("Could not open {}: {e}", answers_path.display()))?;
+        for (part, answer) in &answers {
+            let value = match answer {
+                Answer::Int(n) => n.to_string(),
+                answer => json::quote(&answer.to_string()),
+            };
+            writeln!(answers_file, "{name}.part{part} = {value}")
+                .map_err(|e| format!("Could not write {}: {e}", answers_path.display()))?;
+        }
+        Ok(format!(
+            "Saved {} and its answers to {}; check them before committing\n",
+            path.display(),
+            answers_path.display()
+        ))
+    }
+}
+
+fn format_answers(answers: &[(u32, Result<Answer, String>)], separator: &str) -> String {
+    let answers: Vec<String> = answers
+        .iter()
+        .map(|(part, answer)| match answer {
+            Ok(answer) if answer.is_multiline() => format!("Part {part}:\n{answer}"),
+            Ok(answer) => format!("Part {part}: {answer}"),
+            Err(e) => format!("Part {part} failed: {e}"),
+        })
+        .collect();
+    answers.join(separator)
+}
+
+/// Reads commands from `input` until `quit` or the end of input, writing the responses to
+/// `output`. The prompt is only shown when `interactive`.
+pub fn run(
+    session: &mut Session,
+    input: impl BufRead,
+    mut output: impl Write,
+    interactive: bool,
+) -> io::Result<()> {
+    let prompt =
+        |output: &mut dyn Write, session: &Session| match interactive && !session.is_pasting() {
+            true => {
+                write!(output, "{PROMPT}")?;
+                output.flush()
+            }
+            false => Ok(()),
+        };
+
+    prompt(&mut output, session)?;
+    for line in input.lines() {
+        match session.handle(&line?) {
+            Response::Continue(response) => write!(output, "{response}")?,
+            Response::Quit => return Ok(()),
+        }
+        prompt(&mut output, session)?;
+    }
+    Ok(())
+}
+
+// Every test runs a day, so there are none to run without them.
+#[cfg(all(test, any(feature = "day1", feature = "day6")))]
+mod tests {
+    use super::*;
+
+    fn session() -> Session {
+        Session::new(PathBuf::from("data"), PathBuf::from(fixtures::FIXTURES_DIR))
+    }
+
+    fn respond(session: &mut Session, line: &str) -> String {
+        match session.handle(line) {
+            Response::Continue(response) => response,
+            Response::Quit => "<quit>".into(),
+        }
+    }
+
+    #[cfg(feature = "day1")]
+    #[test]
+    fn test_edit_and_run() {
+        let mut session = session();
+        assert!(respond(&mut session, "run").starts_with("Choose a day"));
+        assert_eq!(
+            respond(&mut session, "day 1"),
+            "2024/day1: Historian Hysteria\n"
+        );
+
+        respond(&mut session, "paste");
+        for line in ["3   4", "4   3", "2   5", "1   3", "3   9", "3   3"] {
+            assert_eq!(respond(&mut session, line), "");
+        }
+        assert_eq!(respond(&mut session, "."), "6 lines in the buffer\n");
+        assert_eq!(respond(&mut session, "run"), "Part 1: 11\nPart 2: 31\n");
+
+        respond(&mut session, "set 6 3   4");
+        respond(&mut session, "delete 1");
+        respond(&mut session, "insert 1 5   5");
+        assert!(respond(&mut session, "show").starts_with("1 | 5   5\n2 | 4   3\n"));
+        assert_eq!(respond(&mut session, "run 2"), "Part 2: 26\n");
+        assert!(respond(&mut session, "delete 9").starts_with("No line 9"));
+
+        respond(&mut session, "append x");
+        assert!(respond(&mut session, "run 1").starts_with("Part 1 failed: panicked: "));
+
+        let history = respond(&mut session, "history");
+        let lines: Vec<&str> = history.lines().collect();
+        assert_eq!(lines[0], "  1  2024/day1  6 lines  Part 1: 11, Part 2: 31");
+        assert_eq!(lines.len(), 3);
+        respond(&mut session, "restore 1");
+        assert_eq!(session.input(), session.history[0].input);
+        assert_eq!(respond(&mut session, "quit"), "<quit>");
+    }
+
+    #[cfg(feature = "day6")]
+    #[test]
+    fn test_render_and_load() {
+        let mut session = session();
+        respond(&mut session, "day 6");
+        respond(&mut session, "load example");
+        assert!(respond(&mut session, "render 1").contains(" ^ "));
+        let frame_count = respond(&mut session, "render 1000");
+        assert!(frame_count.starts_with("There are "));
+        assert_eq!(respond(&mut session, "render 0"), frame_count);
+        let last = frame_count
+            .trim_start_matches("There are ")
+            .trim_end_matches(" frames\n");
+        assert_eq!(
+            respond(&mut session, "render"),
+            respond(&mut session, &format!("render {last}"))
+        );
+        assert!(respond(&mut session, "explain").contains("no explain trace"));
+        assert!(respond(&mut session, "load missing").contains("no fixture missing"));
+    }
+
+    #[cfg(feature = "day1")]
+    #[test]
+    fn test_save() {
+        let fixtures_dir = std::env::temp_dir().join(format!("aoc-repl-{}", std::process::id()));
+        let mut session = Session::new(PathBuf::from("data"), fixtures_dir.clone());
+        respond(&mut session, "day 1");
+        respond(&mut session, "append 1   1");
+        respond(&mut session, "run");
+        assert!(respond(&mut session, "save one").starts_with("Saved "));
+        assert!(respond(&mut session, "save one").contains("already exists"));
+        assert!(respond(&mut session, "save ../x").starts_with("Invalid fixture name"));
+
+        let (fixtures, answers) = fixtures::load(&fixtures_dir, 2024, "day1").unwrap();
+        assert_eq!(fixtures[0].input, "1   1\n");
+        assert_eq!(answers.get("one", 2).unwrap(), &1);
+        fs::remove_dir_all(&fixtures_dir).unwrap();
+    }
+
+    #[cfg(feature = "day1")]
+    #[test]
+    fn test_run_script() {
+        let mut session = session();
+        let script = "day 1\npaste\n1   2\n.\nrun 1\nquit\nrun\n";
+        let mut output = vec![];
+        run(&mut session, script.as_bytes(), &mut output, false).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.ends_with("1 lines in the buffer\nPart 1: 1\n"));
+    }
+}
@@ -0,0 +1,150 @@
+use crate::verify::Verdict;
+use std::time::Duration;
+
+pub const README_PATH: &str = "README.md";
+/// The report replaces whatever is between these markers, so the rest of the README can be
+/// edited by hand.
+const START_MARKER: &str = "<!-- report:start -->";
+const END_MARKER: &str = "<!-- report:end -->";
+
+/// The state of one day as shown in the report.
+pub struct DayStatus {
+    pub year: u32,
+    pub name: &'static str,
+    pub title: &'static str,
+    pub source: String,
+    /// Each part with its verify result and latest bench median.
+    pub parts: Vec<(u32, Verdict, Option<Duration>)>,
+}
+
+impl DayStatus {
+    /// A part counts as solved once its answer is recorded and verify agrees with it.
+    fn stars(&self) -> String {
+        let solved = self
+            .parts
+            .iter()
+            .filter(|(_, verdict, _)| *verdict == Verdict::Pass)
+            .count();
+        match solved {
+            0 => "-".into(),
+            n => "⭐".repeat(n),
+        }
+    }
+
+    fn verify(&self) -> &'static str {
+        let verdicts = self.parts.iter().map(|(_, verdict, _)| *verdict);
+        if verdicts.clone().any(|verdict| verdict == Verdict::Fail) {
+            "❌ fail"
+        } else if verdicts.clone().all(|verdict| verdict == Verdict::Pass) {
+            "✅ pass"
+        } else {
+            "unchecked"
+        }
+    }
+
+    fn medians(&self) -> String {
+        let medians: Vec<String> = self
+            .parts
+            .iter()
+            .map(|(part, _, median)| match median {
+                Some(median) => format!("{part}: {median:.2?}"),
+                None => format!("{part}: -"),
+            })
+            .collect();
+        medians.join(", ")
+    }
+}
+
+pub fn table(days: &[DayStatus]) -> String {
+    let mut table = String::from(
+        "| Year | Day | Title | Stars | Verify | Median runtime | Source |\n\
+         |---|---|---|---|---|---|---|\n",
+    );
+
+    for day in days {
+        table.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} | [{}]({}) |\n",
+            day.year,
+            day.name,
+            day.title,
+            day.stars(),
+            day.verify(),
+            day.medians(),
+            day.source,
+            day.source
+        ));
+    }
+
+    table
+}
+
+/// Puts `table` between the report markers in `readme`, adding the markers at the end if they
+/// are missing, or creating a README if there is none.
+pub fn update_readme(readme: Option<&str>, table: &str) -> String {
+    let section = format!("{START_MARKER}\n{table}{END_MARKER}");
+
+    let Some(readme) = readme else {
+        return format!("# Advent of Code\n\n{section}\n");
+    };
+
+    match (readme.find(START_MARKER), readme.find(END_MARKER)) {
+        (Some(start), Some(end)) if start < end => format!(
+            "{}{section}{}",
+            &readme[..start],
+            &readme[end + END_MARKER.len()..]
+        ),
+        _ => format!("{}\n\n{section}\n", readme.trim_end()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day_status(verdicts: [Verdict; 2]) -> DayStatus {
+        DayStatus {
+            year: 2024,
+            name: "day6",
+            title: "Guard Gallivant",
+            source: "src/advent/y2024/day6.rs".into(),
+            parts: vec![
+                (1, verdicts[0], Some(Duration::from_micros(1500))),
+                (2, verdicts[1], None),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_table() {
+        let table = table(&[
+            day_status([Verdict::Pass, Verdict::Pass]),
+            day_status([Verdict::Pass, Verdict::Unchecked]),
+            day_status([Verdict::Fail, Verdict::Unchecked]),
+        ]);
+        let rows: Vec<&str> = table.lines().skip(2).collect();
+        assert_eq!(
+            rows[0],
+            "| 2024 | day6 | Guard Gallivant | ⭐⭐ | ✅ pass | 1: 1.50ms, 2: - | [src/advent/y2024/day6.rs](src/advent/y2024/day6.rs) |"
+        );
+        assert!(rows[1].contains("| ⭐ | unchecked |"));
+        assert!(rows[2].contains("| - | ❌ fail |"));
+    }
+
+    #[test]
+    fn test_update_readme() {
+        let readme = update_readme(None, "| table |\n");
+        assert_eq!(
+            readme,
+            "# Advent of Code\n\n<!-- report:start -->\n| table |\n<!-- report:end -->\n"
+        );
+
+        let readme = format!("Intro\n\n{readme}Notes\n");
+        let updated = update_readme(Some(&readme), "| new |\n");
+        assert!(updated.starts_with("Intro\n\n# Advent of Code\n\n<!-- report:start -->\n| new |\n<!-- report:end -->\nNotes\n"));
+
+        assert_eq!(
+            update_readme(Some("Hand written\n"), "| t |\n"),
+            "Hand written\n\n<!-- report:start -->\n| t |\n<!-- report:end -->\n"
+        );
+    }
+}
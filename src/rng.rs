@@ -0,0 +1,67 @@
+//! A small seedable random number generator for generated inputs, so the same seed always
+//! gives the same input.
+
+/// SplitMix64, which is fast and good enough for test data, but not for anything secret.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `low..high`, which must not be empty.
+    pub fn range(&mut self, low: u64, high: u64) -> u64 {
+        assert!(low < high, "Empty range {low}..{high}");
+        low + self.next_u64() % (high - low)
+    }
+
+    /// True with probability `numerator / denominator`.
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.range(0, denominator) < numerator
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0, items.len() as u64) as usize]
+    }
+
+    /// Puts `items` in a random order, by Fisher-Yates.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.range(0, i as u64 + 1) as usize);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rng() {
+        let mut rng = Rng::new(7);
+        let numbers: Vec<u64> = (0..100).map(|_| rng.range(10, 20)).collect();
+        assert!(numbers.iter().all(|n| (10..20).contains(n)));
+        assert!((10..20).all(|n| numbers.contains(&n)));
+
+        let mut again = Rng::new(7);
+        assert_eq!(again.range(10, 20), numbers[0]);
+        assert_ne!(Rng::new(8).next_u64(), Rng::new(7).next_u64());
+
+        let mut items: Vec<u64> = (0..20).collect();
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..20).collect::<Vec<_>>());
+        items.sort();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+}
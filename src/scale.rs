@@ -0,0 +1,194 @@
+//! Estimating how a day's running time grows with the size of its input, by timing it on
+//! generated inputs of geometrically increasing size and fitting `time ∝ size^k`.
+
+use crate::{bench, solution::DayRun};
+use std::{fmt::Write, time::Duration};
+
+pub const DEFAULT_MIN_SIZE: usize = 1000;
+pub const DEFAULT_STEPS: usize = 6;
+pub const DEFAULT_SEED: u64 = 2024;
+/// No larger size is measured once a run of a size takes longer than this.
+pub const DEFAULT_BUDGET: Duration = Duration::from_secs(10);
+/// Each size is this many times the one before.
+const GROWTH_FACTOR: usize = 2;
+/// Each size is timed as the median of this many runs.
+const RUNS: usize = 3;
+pub const CSV_HEADER: &str = "year,day,part,size,seconds\n";
+
+/// The median time of each part at each size measured, which may be fewer sizes than asked
+/// for if one went over the budget.
+pub struct Scaling {
+    pub sizes: Vec<usize>,
+    pub parts: Vec<(u32, Vec<Duration>)>,
+}
+
+pub fn sizes(min_size: usize, steps: usize) -> Vec<usize> {
+    (0..steps as u32)
+        .map(|step| min_size * GROWTH_FACTOR.pow(step))
+        .collect()
+}
+
+/// Times `run` on an input made by `generate` at each of `sizes` in turn, stopping after the
+/// first size whose parts take longer than `budget` in all, since larger ones would only take
+/// longer.
+pub fn measure(
+    generate: impl Fn(usize) -> String,
+    run: impl Fn(&str) -> DayRun,
+    sizes: &[usize],
+    budget: Duration,
+) -> Scaling {
+    let mut measured = vec![];
+    let mut parts: Vec<(u32, Vec<Duration>)> = vec![];
+
+    for &size in sizes {
+        let input = generate(size);
+        let medians = bench::bench(|| run(&input), RUNS);
+        for &(part, median) in &medians {
+            match parts.iter_mut().find(|(p, _)| *p == part) {
+                Some((_, times)) => times.push(median),
+                None => parts.push((part, vec![median])),
+            }
+        }
+        measured.push(size);
+        if medians.iter().map(|(_, median)| *median).sum::<Duration>() > budget {
+            break;
+        }
+    }
+
+    Scaling {
+        sizes: measured,
+        parts,
+    }
+}
+
+/// The least-squares slope of log time against log size, so about 1 for linear growth and 2
+/// for quadratic. There is none without two sizes to compare or if any time is zero.
+pub fn growth_exponent(sizes: &[usize], times: &[Duration]) -> Option<f64> {
+    if sizes.len() < 2 || times.iter().any(Duration::is_zero) {
+        return None;
+    }
+
+    let points: Vec<(f64, f64)> = sizes
+        .iter()
+        .zip(times)
+        .map(|(&size, time)| ((size as f64).ln(), time.as_secs_f64().ln()))
+        .collect();
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let covariance: f64 = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    Some(covariance / variance)
+}
+
+fn format_exponent(exponent: Option<f64>) -> String {
+    exponent.map_or("-".into(), |exponent| format!("n^{exponent:.2}"))
+}
+
+/// One row per size with each part's time, then each part's fitted exponent.
+pub fn table(scaling: &Scaling) -> String {
+    let mut table = format!("{:>10}", "size");
+    for (part, _) in &scaling.parts {
+        write!(table, "{:>14}", format!("part {part}")).unwrap();
+    }
+    table.push('\n');
+
+    for (i, size) in scaling.sizes.iter().enumerate() {
+        write!(table, "{size:>10}").unwrap();
+        for (_, times) in &scaling.parts {
+            write!(table, "{:>14}", format!("{:.2?}", times[i])).unwrap();
+        }
+        table.push('\n');
+    }
+
+    write!(table, "{:>10}", "growth").unwrap();
+    for (_, times) in &scaling.parts {
+        let exponent = growth_exponent(&scaling.sizes, times);
+        write!(table, "{:>14}", format_exponent(exponent)).unwrap();
+    }
+    table.push('\n');
+    table
+}
+
+/// Rows for [`CSV_HEADER`], one per part and size.
+pub fn csv_rows(year: u32, day: &str, scaling: &Scaling) -> String {
+    let mut rows = String::new();
+    for (part, times) in &scaling.parts {
+        for (size, time) in scaling.sizes.iter().zip(times) {
+            writeln!(rows, "{year},{day},{part},{size},{}", time.as_secs_f64()).unwrap();
+        }
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::PartRun;
+
+    fn scaling() -> Scaling {
+        let sizes = sizes(100, 4);
+        let linear = sizes.iter().map(|&n| Duration::from_micros(n as u64 * 3));
+        let quadratic = sizes.iter().map(|&n| Duration::from_nanos((n * n) as u64));
+        Scaling {
+            parts: vec![(1, linear.collect()), (2, quadratic.collect())],
+            sizes,
+        }
+    }
+
+    #[test]
+    fn test_growth_exponent() {
+        let scaling = scaling();
+        assert_eq!(scaling.sizes, vec![100, 200, 400, 800]);
+        let exponent = |part: usize| growth_exponent(&scaling.sizes, &scaling.parts[part].1);
+        assert!((exponent(0).unwrap() - 1.0).abs() < 1e-9);
+        assert!((exponent(1).unwrap() - 2.0).abs() < 1e-9);
+        assert_eq!(growth_exponent(&[10], &[Duration::from_secs(1)]), None);
+        assert_eq!(growth_exponent(&[1, 2], &[Duration::ZERO; 2]), None);
+    }
+
+    #[test]
+    fn test_table_and_csv() {
+        let table = table(&scaling());
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "      size        part 1        part 2");
+        assert_eq!(lines[1], "       100      300.00µs       10.00µs");
+        assert_eq!(lines[5], "    growth        n^1.00        n^2.00");
+
+        let csv = csv_rows(2024, "day1", &scaling());
+        assert!(csv.starts_with("2024,day1,1,100,0.0003\n"));
+        assert_eq!(csv.lines().count(), 8);
+    }
+
+    #[test]
+    fn test_measure() {
+        let run = |input: &str| DayRun {
+            phases: vec![],
+            parts: vec![PartRun {
+                part: 1,
+                answer: Ok(input.len().into()),
+                elapsed: Duration::from_millis(input.len() as u64),
+            }],
+        };
+        let scaling = measure(|size| "x".repeat(size), run, &[1, 2], DEFAULT_BUDGET);
+        assert_eq!(
+            scaling.parts,
+            vec![(1, vec![Duration::from_millis(1), Duration::from_millis(2)])]
+        );
+
+        // The size that goes over the budget is the last one measured.
+        let budget = Duration::from_millis(15);
+        let scaling = measure(|size| "x".repeat(size), run, &[10, 20, 40], budget);
+        assert_eq!(scaling.sizes, vec![10, 20]);
+        assert_eq!(
+            scaling.parts,
+            vec![(
+                1,
+                vec![Duration::from_millis(10), Duration::from_millis(20)]
+            )]
+        );
+    }
+}
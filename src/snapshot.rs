@@ -0,0 +1,106 @@
+//! Snapshot tests of rendered text. A snapshot is the expected rendering kept in
+//! `tests/snapshots/<name>.snap`; a test fails with a line diff if its rendering changes.
+//!
+//! Run the tests with `SNAPSHOT_ACCEPT=1` to write the new renderings as the snapshots
+//! instead, then review the changes to the files before committing them.
+
+use std::{env, fs, path::PathBuf};
+
+pub const ACCEPT_VAR: &str = "SNAPSHOT_ACCEPT";
+const SNAPSHOT_DIR: &str = "tests/snapshots";
+
+fn snapshot_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join(SNAPSHOT_DIR)
+        .join(format!("{name}.snap"))
+}
+
+fn accepting() -> bool {
+    env::var_os(ACCEPT_VAR).is_some_and(|value| !value.is_empty() && value != "0")
+}
+
+/// Panics unless `actual` is the same as the snapshot called `name`, or writes it as the
+/// snapshot if [`ACCEPT_VAR`] is set.
+#[track_caller]
+pub fn assert_snapshot(name: &str, actual: &str) {
+    let path = snapshot_path(name);
+    let expected = fs::read_to_string(&path).ok();
+    if expected.as_deref() == Some(actual) {
+        return;
+    }
+
+    if accepting() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, actual)
+            .unwrap_or_else(|e| panic!("Could not write {}: {e}", path.display()));
+        return;
+    }
+
+    match expected {
+        None => panic!(
+            "No snapshot at {}; run with {ACCEPT_VAR}=1 to record it. The rendering was:\n{actual}",
+            path.display()
+        ),
+        Some(expected) => panic!(
+            "Snapshot {name} does not match; run with {ACCEPT_VAR}=1 to accept the change.\n\
+             --- expected\n+++ actual\n{}",
+            diff(&expected, actual)
+        ),
+    }
+}
+
+/// A line diff with `-` before lines only in `expected`, `+` before lines only in `actual`,
+/// and a space before lines in both. Line ends are shown as `$` so that changes in trailing
+/// whitespace are visible.
+pub fn diff(expected: &str, actual: &str) -> String {
+    let old: Vec<&str> = expected.lines().collect();
+    let new: Vec<&str> = actual.lines().collect();
+
+    // common[i][j] is the length of the longest common subsequence of old[i..] and new[j..].
+    let mut common = vec![vec![0; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = String::new();
+    let mut push = |sign: char, line: &str| {
+        diff.push(sign);
+        diff.push_str(line);
+        diff.push_str("$\n");
+    };
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            push(' ', old[i]);
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || common[i + 1][j] >= common[i][j + 1]) {
+            push('-', old[i]);
+            i += 1;
+        } else {
+            push('+', new[j]);
+            j += 1;
+        }
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        assert_eq!(
+            diff("a\nb\nc\n", "a\nB\nc\nd \n"),
+            " a$\n-b$\n+B$\n c$\n+d $\n"
+        );
+        assert_eq!(diff("same\n", "same\n"), " same$\n");
+    }
+}
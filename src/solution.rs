@@ -0,0 +1,295 @@
+use crate::{answer::Answer, input::InputSource};
+use std::{
+    any::Any,
+    fmt, io,
+    panic::{self, AssertUnwindSafe},
+    time::{Duration, Instant},
+};
+
+/// A day's solution, split into phases so that work common to both parts runs once.
+pub trait Solution {
+    /// The parsed puzzle input.
+    type Input;
+    /// Work shared by both parts, computed once after parsing. `()` when there is none.
+    type Prepared;
+
+    fn parse(input: &str) -> Self::Input;
+    fn prepare(input: &Self::Input) -> Self::Prepared;
+    fn part1(input: &Self::Input, prepared: &Self::Prepared) -> PartResult;
+    fn part2(input: &Self::Input, prepared: &Self::Prepared) -> PartResult;
+}
+
+/// A day that can also solve its parts straight from an input stream, in memory that does not
+/// grow with the input. Each part reads the input again.
+pub trait StreamSolution {
+    fn part1(source: &InputSource) -> PartResult;
+    fn part2(source: &InputSource) -> PartResult;
+}
+
+/// Why a part has no answer for an input that it could parse.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SolveError {
+    /// Rules that contradict each other, given as the cycle they form.
+    Cycle(String),
+    /// The guard walks in a loop instead of leaving the map.
+    GuardLoops,
+    /// A page that appears more than once in an update, given with the update.
+    RepeatedPage(String),
+    /// An input too small or too simple for the puzzle to make sense.
+    Degenerate(String),
+    /// The input could not be read while streaming it.
+    Read(String),
+}
+
+pub type PartResult = Result<Answer, SolveError>;
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::Cycle(cycle) => write!(f, "Rules form a cycle: {cycle}"),
+            SolveError::GuardLoops => write!(f, "The guard walks in a loop"),
+            SolveError::RepeatedPage(page) => write!(f, "Page repeats: {page}"),
+            SolveError::Degenerate(reason) => write!(f, "Degenerate input: {reason}"),
+            SolveError::Read(e) => write!(f, "Could not read input: {e}"),
+        }
+    }
+}
+
+/// The answer, or the error prefixed with `error: `.
+pub fn format_result(result: &PartResult) -> String {
+    match result {
+        Ok(answer) => answer.to_string(),
+        Err(e) => format!("error: {e}"),
+    }
+}
+
+impl From<io::Error> for SolveError {
+    fn from(e: io::Error) -> Self {
+        SolveError::Read(e.to_string())
+    }
+}
+
+/// A day with other ways of solving its parts, kept to compare against `part1` and `part2`.
+pub trait Variants: Solution + Sized {
+    fn variants() -> Vec<Variant<Self>>;
+}
+
+pub type PartFn<S> = fn(&<S as Solution>::Input, &<S as Solution>::Prepared) -> PartResult;
+
+/// A named way of solving one part.
+pub struct Variant<S: Solution> {
+    pub part: u32,
+    pub name: &'static str,
+    pub solve: PartFn<S>,
+}
+
+/// What `part1` and `part2` are called among a day's variants.
+pub const MAIN_VARIANT: &str = "main";
+
+pub struct PartRun {
+    pub part: u32,
+    pub answer: Result<Answer, String>,
+    pub elapsed: Duration,
+}
+
+/// The answers from running a day, with the time taken by each phase and each part.
+#[derive(Default)]
+pub struct DayRun {
+    pub phases: Vec<(&'static str, Duration)>,
+    pub parts: Vec<PartRun>,
+}
+
+pub fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Runs `f`, turning a panic into an error with its message.
+pub fn catch_panic<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| panic_message(&*payload))
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "panicked".into()
+    }
+}
+
+/// Parses `input` and drops it.
+pub fn parse<S: Solution>(input: &str) {
+    S::parse(input);
+}
+
+/// Parses and prepares `input` once, then runs each of `parts`.
+pub fn run<S: Solution>(input: &str, parts: &[u32]) -> DayRun {
+    let (input, parse_elapsed) = timed(|| S::parse(input));
+    let (prepared, prepare_elapsed) = timed(|| S::prepare(&input));
+
+    let part_fns: [PartFn<S>; 2] = [S::part1, S::part2];
+    let parts = part_fns
+        .iter()
+        .zip(1..)
+        .filter(|(_part_fn, part)| parts.contains(part))
+        .map(|(part_fn, part)| {
+            let (answer, elapsed) = timed(|| part_fn(&input, &prepared));
+            PartRun {
+                part,
+                answer: answer.map_err(|e| e.to_string()),
+                elapsed,
+            }
+        })
+        .collect();
+
+    DayRun {
+        phases: vec![("parse", parse_elapsed), ("prepare", prepare_elapsed)],
+        parts,
+    }
+}
+
+pub struct VariantRun {
+    pub part: u32,
+    pub name: &'static str,
+    pub answer: PartResult,
+    pub elapsed: Duration,
+}
+
+/// How a day runs its variants, e.g. `run_variants::<day1::Solver>`.
+pub type RunVariants = fn(&str, &[u32]) -> Vec<VariantRun>;
+
+/// Parses and prepares `input` once, then runs the main solution and every variant of each of
+/// `parts`.
+pub fn run_variants<S: Variants>(input: &str, parts: &[u32]) -> Vec<VariantRun> {
+    let input = S::parse(input);
+    let prepared = S::prepare(&input);
+
+    let main = [(1, S::part1 as PartFn<S>), (2, S::part2)].map(|(part, solve)| Variant {
+        part,
+        name: MAIN_VARIANT,
+        solve,
+    });
+    let mut variants: Vec<Variant<S>> = main.into_iter().chain(S::variants()).collect();
+    variants.sort_by_key(|variant| variant.part);
+
+    variants
+        .into_iter()
+        .filter(|variant| parts.contains(&variant.part))
+        .map(|variant| {
+            let (answer, elapsed) = timed(|| (variant.solve)(&input, &prepared));
+            VariantRun {
+                part: variant.part,
+                name: variant.name,
+                answer,
+                elapsed,
+            }
+        })
+        .collect()
+}
+
+/// Runs each of `parts` on `source` as a stream. Errors reading the input fail that part.
+pub fn run_stream<S: StreamSolution>(source: &InputSource, parts: &[u32]) -> DayRun {
+    let part_fns: [fn(&InputSource) -> PartResult; 2] = [S::part1, S::part2];
+    let parts = part_fns
+        .iter()
+        .zip(1..)
+        .filter(|(_part_fn, part)| parts.contains(part))
+        .map(|(part_fn, part)| {
+            let (answer, elapsed) = timed(|| part_fn(source));
+            PartRun {
+                part,
+                answer: answer.map_err(|e| e.to_string()),
+                elapsed,
+            }
+        })
+        .collect();
+
+    DayRun {
+        phases: vec![],
+        parts,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    thread_local! {
+        static PREPARE_CALLS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    struct WordCount;
+
+    impl Solution for WordCount {
+        type Input = Vec<String>;
+        type Prepared = usize;
+
+        fn parse(input: &str) -> Self::Input {
+            input.split_whitespace().map(String::from).collect()
+        }
+
+        fn prepare(input: &Self::Input) -> Self::Prepared {
+            PREPARE_CALLS.with(|calls| calls.set(calls.get() + 1));
+            input.len()
+        }
+
+        fn part1(_input: &Self::Input, prepared: &Self::Prepared) -> PartResult {
+            Ok((*prepared).into())
+        }
+
+        fn part2(input: &Self::Input, prepared: &Self::Prepared) -> PartResult {
+            if *prepared == 0 {
+                return Err(SolveError::Degenerate("no words".into()));
+            }
+            Ok((input.iter().map(String::len).sum::<usize>() / prepared).into())
+        }
+    }
+
+    #[test]
+    fn test_run() {
+        let day_run = run::<WordCount>("ab cdef", &[1, 2]);
+
+        let phases: Vec<&str> = day_run.phases.iter().map(|(name, _)| *name).collect();
+        assert_eq!(phases, vec!["parse", "prepare"]);
+        assert_eq!(PREPARE_CALLS.with(Cell::get), 1);
+
+        let answers: Vec<Answer> = day_run
+            .parts
+            .into_iter()
+            .map(|part| part.answer.unwrap())
+            .collect();
+        assert_eq!(answers, vec![Answer::from(2), Answer::from(3)]);
+
+        let day_run = run::<WordCount>("ab cdef", &[2]);
+        let parts: Vec<u32> = day_run.parts.iter().map(|part| part.part).collect();
+        assert_eq!(parts, vec![2]);
+
+        let day_run = run::<WordCount>("", &[2]);
+        let error = day_run.parts[0].answer.as_ref().unwrap_err();
+        assert_eq!(error, "Degenerate input: no words");
+    }
+
+    impl Variants for WordCount {
+        fn variants() -> Vec<Variant<Self>> {
+            vec![Variant {
+                part: 1,
+                name: "recount",
+                solve: |input, _prepared| Ok(input.len().into()),
+            }]
+        }
+    }
+
+    #[test]
+    fn test_run_variants() {
+        let runs = run_variants::<WordCount>("ab cdef", &[1, 2]);
+        let names: Vec<(u32, &str)> = runs.iter().map(|run| (run.part, run.name)).collect();
+        assert_eq!(names, vec![(1, "main"), (1, "recount"), (2, "main")]);
+        assert!(runs[..2].iter().all(|run| run.answer == Ok(2.into())));
+
+        assert_eq!(run_variants::<WordCount>("ab", &[2]).len(), 1);
+    }
+}
@@ -0,0 +1,229 @@
+//! Run specs, which select the days and parts a command works on.
+//!
+//! A spec is an optional year and `:`, a comma-separated list of days, then optionally `:` and
+//! the parts to run. A day is written `6` or `day6`, a range `1..5` includes both ends, and
+//! `all` is every enabled day. Days without a year are from the latest enabled year, except
+//! that `all` on its own covers every year. A year on its own selects all of its days. For
+//! example `6`, `day6:2`, `1..5`, `1,3,7:1`, `all:2`, `2024` and `2024:6:2`. Several specs
+//! select everything any of them selects.
+
+use std::collections::BTreeSet;
+
+pub const PARTS: [u32; 2] = [1, 2];
+const LAST_DAY: u32 = 25;
+const FIRST_YEAR: u32 = 2015;
+
+/// A day selected by a spec, with the parts to run in order.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Target {
+    pub year: u32,
+    pub day: u32,
+    pub parts: Vec<u32>,
+}
+
+/// Parses `specs` against the enabled `days`, given as `(year, day)`. No specs selects every
+/// part of every day.
+pub fn parse(specs: &[String], days: &[(u32, u32)]) -> Result<Vec<Target>, String> {
+    let mut selected: BTreeSet<(u32, u32, u32)> = BTreeSet::new();
+    let select_all = |selected: &mut BTreeSet<_>, parts: &[u32]| {
+        for &(year, day) in days {
+            selected.extend(parts.iter().map(|&part| (year, day, part)));
+        }
+    };
+
+    if specs.is_empty() {
+        select_all(&mut selected, &PARTS);
+    }
+    for spec in specs {
+        let (year, rest) = match spec.split_once(':') {
+            Some((year_str, rest)) if is_year(year_str) => {
+                (Some(parse_year(year_str, days)?), rest)
+            }
+            _ if is_year(spec) => (Some(parse_year(spec, days)?), "all"),
+            _ => (None, spec.as_str()),
+        };
+        let (days_str, parts_str) = match rest.split_once(':') {
+            Some((days_str, parts_str)) => (days_str, Some(parts_str)),
+            None => (rest, None),
+        };
+        let spec_parts = match parts_str {
+            Some(parts_str) => parse_parts(parts_str)?,
+            None => PARTS.to_vec(),
+        };
+
+        if year.is_none() && days_str == "all" {
+            select_all(&mut selected, &spec_parts);
+            continue;
+        }
+        let year = year.unwrap_or_else(|| latest_year(days));
+        let year_days: Vec<u32> = days
+            .iter()
+            .filter(|(day_year, _)| *day_year == year)
+            .map(|(_, day)| *day)
+            .collect();
+        for day in parse_days(days_str, &year_days)? {
+            selected.extend(spec_parts.iter().map(|&part| (year, day, part)));
+        }
+    }
+
+    let mut targets: Vec<Target> = vec![];
+    for (year, day, part) in selected {
+        match targets.last_mut() {
+            Some(target) if (target.year, target.day) == (year, day) => target.parts.push(part),
+            _ => targets.push(Target {
+                year,
+                day,
+                parts: vec![part],
+            }),
+        }
+    }
+    Ok(targets)
+}
+
+/// Years are told apart from days by having four digits.
+fn is_year(s: &str) -> bool {
+    s.len() == 4 && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn parse_year(year_str: &str, days: &[(u32, u32)]) -> Result<u32, String> {
+    match year_str.parse() {
+        Ok(year) if days.iter().any(|(day_year, _)| *day_year == year) => Ok(year),
+        Ok(year) if year >= FIRST_YEAR => Err(format!("Unknown year: {year_str}")),
+        _ => Err(format!("Invalid year: {year_str}")),
+    }
+}
+
+fn latest_year(days: &[(u32, u32)]) -> u32 {
+    days.iter().map(|(year, _)| *year).max().unwrap_or_default()
+}
+
+fn parse_parts(parts_str: &str) -> Result<Vec<u32>, String> {
+    parts_str
+        .split(',')
+        .map(|part_str| match part_str.parse() {
+            Ok(part) if PARTS.contains(&part) => Ok(part),
+            _ => Err(format!("Invalid part: {part_str}")),
+        })
+        .collect()
+}
+
+fn parse_days(days_str: &str, days: &[u32]) -> Result<Vec<u32>, String> {
+    if days_str == "all" {
+        return Ok(days.to_vec());
+    }
+
+    let mut selected = vec![];
+    for item in days_str.split(',') {
+        match item.split_once("..") {
+            Some((first, last)) => {
+                let invalid_range = || format!("Invalid range: {item}");
+                let first = parse_day_number(first).map_err(|_| invalid_range())?;
+                let last = parse_day_number(last).map_err(|_| invalid_range())?;
+                if first > last {
+                    return Err(invalid_range());
+                }
+                let in_range: Vec<u32> = days
+                    .iter()
+                    .copied()
+                    .filter(|day| (first..=last).contains(day))
+                    .collect();
+                if in_range.is_empty() {
+                    return Err(format!("No enabled days in range: {item}"));
+                }
+                selected.extend(in_range);
+            }
+            None => {
+                let day = parse_day_number(item)?;
+                if !days.contains(&day) {
+                    return Err(format!("Unknown day: {item}"));
+                }
+                selected.push(day);
+            }
+        }
+    }
+    Ok(selected)
+}
+
+fn parse_day_number(day_str: &str) -> Result<u32, String> {
+    let number = day_str.strip_prefix("day").unwrap_or(day_str);
+    match number.parse() {
+        Ok(day) if (1..=LAST_DAY).contains(&day) => Ok(day),
+        _ => Err(format!("Invalid day: {day_str}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAYS: [(u32, u32); 5] = [(2024, 1), (2024, 2), (2024, 3), (2024, 6), (2024, 7)];
+
+    fn targets(specs: &[&str]) -> Result<Vec<(u32, Vec<u32>)>, String> {
+        let specs: Vec<String> = specs.iter().map(|spec| spec.to_string()).collect();
+        let targets = parse(&specs, &DAYS)?;
+        Ok(targets
+            .into_iter()
+            .map(|target| (target.day, target.parts))
+            .collect())
+    }
+
+    #[test]
+    fn test_parse_years() {
+        let days = [(2023, 1), (2023, 25), (2024, 1), (2024, 6)];
+        let targets = |specs: &[&str]| -> Result<Vec<(u32, u32, Vec<u32>)>, String> {
+            let specs: Vec<String> = specs.iter().map(|spec| spec.to_string()).collect();
+            Ok(parse(&specs, &days)?
+                .into_iter()
+                .map(|target| (target.year, target.day, target.parts))
+                .collect())
+        };
+
+        assert_eq!(targets(&["2024:6:2"]), Ok(vec![(2024, 6, vec![2])]));
+        assert_eq!(targets(&["1"]), Ok(vec![(2024, 1, vec![1, 2])]));
+        assert_eq!(
+            targets(&["2023:1..25:1"]),
+            Ok(vec![(2023, 1, vec![1]), (2023, 25, vec![1])])
+        );
+        assert_eq!(targets(&["2023"]).unwrap().len(), 2);
+        assert_eq!(targets(&["all:1"]).unwrap().len(), 4);
+        assert_eq!(targets(&["2024:all"]).unwrap().len(), 2);
+        assert_eq!(targets(&["2022:1"]), Err("Unknown year: 2022".into()));
+        assert_eq!(targets(&["0001:1"]), Err("Invalid year: 0001".into()));
+        assert_eq!(targets(&["2023:6"]), Err("Unknown day: 6".into()));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(targets(&["6"]), Ok(vec![(6, vec![1, 2])]));
+        assert_eq!(targets(&["day6:2"]), Ok(vec![(6, vec![2])]));
+        assert_eq!(
+            targets(&["1..5"]),
+            Ok(vec![(1, vec![1, 2]), (2, vec![1, 2]), (3, vec![1, 2])])
+        );
+        assert_eq!(
+            targets(&["7,1,3:1"]),
+            Ok(vec![(1, vec![1]), (3, vec![1]), (7, vec![1])])
+        );
+        assert_eq!(targets(&["all:2"]).unwrap().len(), DAYS.len());
+        assert_eq!(
+            targets(&["6:2", "day6:1", "2:1,2"]),
+            Ok(vec![(2, vec![1, 2]), (6, vec![1, 2])])
+        );
+        assert_eq!(targets(&[]).unwrap().len(), DAYS.len());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(targets(&["day4"]), Err("Unknown day: day4".into()));
+        assert_eq!(targets(&["1,x"]), Err("Invalid day: x".into()));
+        assert_eq!(targets(&["26"]), Err("Invalid day: 26".into()));
+        assert_eq!(targets(&["5..1"]), Err("Invalid range: 5..1".into()));
+        assert_eq!(targets(&["1..30"]), Err("Invalid range: 1..30".into()));
+        assert_eq!(
+            targets(&["8..10"]),
+            Err("No enabled days in range: 8..10".into())
+        );
+        assert_eq!(targets(&["6:3"]), Err("Invalid part: 3".into()));
+        assert_eq!(targets(&["all:"]), Err("Invalid part: ".into()));
+    }
+}
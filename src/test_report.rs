@@ -0,0 +1,243 @@
+//! Reports of answer checks in formats that CI systems understand: JUnit XML and TAP.
+
+use crate::{
+    json,
+    verify::{PartCheck, Verdict},
+};
+use std::{fmt::Write, str::FromStr};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportFormat {
+    Junit,
+    Tap,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "junit" => Ok(ReportFormat::Junit),
+            "tap" => Ok(ReportFormat::Tap),
+            _ => Err(format!("Invalid report format: {s}")),
+        }
+    }
+}
+
+pub fn render(format: ReportFormat, suite: &str, checks: &[PartCheck]) -> String {
+    match format {
+        ReportFormat::Junit => junit(suite, checks),
+        ReportFormat::Tap => tap(checks),
+    }
+}
+
+/// The expected and actual answers of a failed check, one per line.
+fn failure_details(check: &PartCheck) -> Vec<(&'static str, String)> {
+    let mut details = vec![];
+    if let Some(expected) = &check.expected {
+        details.push(("expected", expected.to_string()));
+    }
+    match &check.actual {
+        Ok(actual) => details.push(("actual", actual.to_string())),
+        Err(e) => details.push(("error", e.clone())),
+    }
+    details
+}
+
+fn failure_message(check: &PartCheck) -> String {
+    match (&check.expected, &check.actual) {
+        (_, Err(e)) => format!("error: {e}"),
+        (Some(expected), Ok(actual)) => format!("expected {expected}, got {actual}"),
+        (None, Ok(actual)) => format!("got {actual}"),
+    }
+}
+
+/// One `<testsuite>` per day, named like `2024/day6`, and one `<testcase>` per part and input.
+/// Checks without a recorded answer are reported as skipped.
+pub fn junit(suite: &str, checks: &[PartCheck]) -> String {
+    let count = |checks: &[&PartCheck], verdict| {
+        checks
+            .iter()
+            .filter(|check| check.verdict() == verdict)
+            .count()
+    };
+    let seconds = |checks: &[&PartCheck]| -> f64 {
+        checks.iter().map(|check| check.elapsed.as_secs_f64()).sum()
+    };
+
+    let mut days: Vec<(String, Vec<&PartCheck>)> = vec![];
+    for check in checks {
+        let day_id = check.day_id();
+        match days.iter_mut().find(|(day, _)| *day == day_id) {
+            Some((_, day_checks)) => day_checks.push(check),
+            None => days.push((day_id, vec![check])),
+        }
+    }
+
+    let all: Vec<&PartCheck> = checks.iter().collect();
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    writeln!(
+        xml,
+        "<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.6}\">",
+        xml_escape(suite),
+        all.len(),
+        count(&all, Verdict::Fail),
+        count(&all, Verdict::Unchecked),
+        seconds(&all)
+    )
+    .unwrap();
+
+    for (day, day_checks) in &days {
+        writeln!(
+            xml,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.6}\">",
+            xml_escape(day),
+            day_checks.len(),
+            count(day_checks, Verdict::Fail),
+            count(day_checks, Verdict::Unchecked),
+            seconds(day_checks)
+        )
+        .unwrap();
+
+        for check in day_checks {
+            write!(
+                xml,
+                "    <testcase classname=\"{}.{}.{}\" name=\"{}\" time=\"{:.6}\"",
+                xml_escape(suite),
+                check.year,
+                xml_escape(&check.day),
+                xml_escape(&check.name()),
+                check.elapsed.as_secs_f64()
+            )
+            .unwrap();
+            match check.verdict() {
+                Verdict::Pass => xml.push_str("/>\n"),
+                Verdict::Unchecked => xml.push_str(
+                    ">\n      <skipped message=\"no recorded answer\"/>\n    </testcase>\n",
+                ),
+                Verdict::Fail => {
+                    let details: Vec<String> = failure_details(check)
+                        .iter()
+                        .map(|(key, value)| format!("{key}: {value}"))
+                        .collect();
+                    writeln!(
+                        xml,
+                        ">\n      <failure message=\"{}\">{}</failure>\n    </testcase>",
+                        xml_escape(&failure_message(check)),
+                        xml_escape(&details.join("\n"))
+                    )
+                    .unwrap();
+                }
+            }
+        }
+
+        xml.push_str("  </testsuite>\n");
+    }
+
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+/// TAP version 13, with a YAML block giving each check's time, and its answers if it failed.
+pub fn tap(checks: &[PartCheck]) -> String {
+    let mut tap = format!("TAP version 13\n1..{}\n", checks.len());
+
+    for (i, check) in checks.iter().enumerate() {
+        let (status, directive) = match check.verdict() {
+            Verdict::Pass => ("ok", ""),
+            Verdict::Unchecked => ("ok", " # SKIP no recorded answer"),
+            Verdict::Fail => ("not ok", ""),
+        };
+        writeln!(tap, "{status} {} - {}{directive}", i + 1, check.name()).unwrap();
+
+        tap.push_str("  ---\n");
+        writeln!(
+            tap,
+            "  duration_ms: {:.3}",
+            check.elapsed.as_secs_f64() * 1000.0
+        )
+        .unwrap();
+        if check.verdict() == Verdict::Fail {
+            for (key, value) in failure_details(check) {
+                writeln!(tap, "  {key}: {}", json::quote(&value)).unwrap();
+            }
+        }
+        tap.push_str("  ...\n");
+    }
+
+    tap
+}
+
+fn xml_escape(s: &str) -> String {
+    let mut escaped = String::new();
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn checks() -> Vec<PartCheck> {
+        let check =
+            |input: &str, part, expected: Option<i32>, actual: Result<i32, &str>| PartCheck {
+                year: 2024,
+                day: "day8".into(),
+                input: input.into(),
+                part,
+                expected: expected.map(Into::into),
+                actual: actual.map(Into::into).map_err(String::from),
+                elapsed: Duration::from_millis(2),
+            };
+        vec![
+            check("day8", 1, Some(14), Ok(14)),
+            check("day8", 2, Some(34), Ok(35)),
+            check("example", 1, None, Ok(3)),
+            check("example", 2, Some(9), Err("<panic>")),
+        ]
+    }
+
+    #[test]
+    fn test_junit() {
+        let xml = junit("verify", &checks());
+        assert!(xml.contains(
+            "<testsuites name=\"verify\" tests=\"4\" failures=\"2\" skipped=\"1\" time=\"0.008000\">"
+        ));
+        assert!(xml.contains(
+            "<testcase classname=\"verify.2024.day8\" name=\"2024/day8 part 1\" time=\"0.002000\"/>\n"
+        ));
+        assert!(xml.contains(
+            "<failure message=\"expected 34, got 35\">expected: 34\nactual: 35</failure>"
+        ));
+        assert!(
+            xml.contains("name=\"2024/day8 part 1 (example)\" time=\"0.002000\">\n      <skipped")
+        );
+        assert!(xml.contains("<failure message=\"error: &lt;panic&gt;\">"));
+        assert_eq!(xml.matches("<testsuite ").count(), 1);
+    }
+
+    #[test]
+    fn test_tap() {
+        let tap = tap(&checks());
+        let lines: Vec<&str> = tap.lines().collect();
+        assert_eq!(
+            lines[..3],
+            ["TAP version 13", "1..4", "ok 1 - 2024/day8 part 1"]
+        );
+        assert!(tap.contains(
+            "not ok 2 - 2024/day8 part 2\n  ---\n  duration_ms: 2.000\n  expected: \"34\"\n  actual: \"35\"\n  ...\n"
+        ));
+        assert!(tap.contains("ok 3 - 2024/day8 part 1 (example) # SKIP no recorded answer\n"));
+        assert!(tap.contains("  error: \"<panic>\"\n"));
+    }
+}
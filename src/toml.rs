@@ -0,0 +1,121 @@
+/// A `key = value` line from a file in the flat subset of TOML that the runner reads.
+#[derive(Debug, PartialEq)]
+pub struct Entry {
+    pub line: usize,
+    pub key: String,
+    pub value: String,
+    /// Whether the value was a string, rather than a bare number or word.
+    pub quoted: bool,
+}
+
+/// Parses `key = value` lines, skipping blank lines and `#` comments and unquoting strings.
+/// Errors are prefixed with the line number.
+pub fn entries(toml_str: &str) -> Result<Vec<Entry>, String> {
+    let mut entries = vec![];
+
+    for (i, line) in toml_str.lines().enumerate() {
+        let line = strip_comment(line).trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| format!("{}: expected `key = value`", i + 1))?;
+        let value = value.trim();
+        entries.push(Entry {
+            line: i + 1,
+            key: key.trim().to_string(),
+            value: unquote(value).map_err(|e| format!("{}: {e}", i + 1))?,
+            quoted: value.starts_with('"'),
+        });
+    }
+
+    Ok(entries)
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+fn unquote(value: &str) -> Result<String, String> {
+    let Some(quoted) = value.strip_prefix('"') else {
+        return Ok(value.to_string());
+    };
+    let unterminated = || format!("Unterminated string: {value}");
+
+    let mut unquoted = String::new();
+    let mut chars = quoted.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if chars.as_str().is_empty() => return Ok(unquoted),
+            '"' => return Err(format!("Unexpected text after string: {value}")),
+            '\\' => match chars.next() {
+                Some('n') => unquoted.push('\n'),
+                Some(c @ ('"' | '\\')) => unquoted.push(c),
+                Some(c) => return Err(format!("Invalid escape \\{c} in {value}")),
+                None => return Err(unterminated()),
+            },
+            c => unquoted.push(c),
+        }
+    }
+
+    Err(unterminated())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entries() {
+        let toml_str = "# header\n\
+                        day6.part1 = 5534 # comment\n\
+                        \n\
+                        day9.part2 = \"a \\\"#\\\" b\\nc\"\n";
+        let entries = entries(toml_str).unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                Entry {
+                    line: 2,
+                    key: "day6.part1".into(),
+                    value: "5534".into(),
+                    quoted: false,
+                },
+                Entry {
+                    line: 4,
+                    key: "day9.part2".into(),
+                    value: "a \"#\" b\nc".into(),
+                    quoted: true,
+                },
+            ]
+        );
+
+        assert_eq!(
+            super::entries("x = \"abc").unwrap_err(),
+            "1: Unterminated string: \"abc"
+        );
+    }
+
+    #[test]
+    fn test_unquote() {
+        assert_eq!(unquote("\"a\\\\b\\nc\"").unwrap(), "a\\b\nc");
+        assert_eq!(unquote("42").unwrap(), "42");
+        assert_eq!(
+            unquote("\"a\" b").unwrap_err(),
+            "Unexpected text after string: \"a\" b"
+        );
+    }
+}
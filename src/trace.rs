@@ -0,0 +1,118 @@
+use crate::json;
+use std::fmt;
+
+#[derive(Debug, Default)]
+pub struct Trace {
+    parts: Vec<PartTrace>,
+}
+
+#[derive(Debug)]
+pub struct PartTrace {
+    part: u8,
+    answer: String,
+    steps: Vec<Step>,
+}
+
+#[derive(Debug)]
+struct Step {
+    line: Option<usize>,
+    kind: &'static str,
+    message: String,
+}
+
+impl Trace {
+    pub fn part(&mut self, part: u8, answer: impl ToString) -> &mut PartTrace {
+        self.parts.push(PartTrace {
+            part,
+            answer: answer.to_string(),
+            steps: vec![],
+        });
+        self.parts.last_mut().unwrap()
+    }
+
+    pub fn retain_parts(&mut self, parts: &[u32]) {
+        self.parts
+            .retain(|part_trace| parts.contains(&part_trace.part.into()));
+    }
+
+    pub fn to_json(&self) -> String {
+        let parts: Vec<String> = self.parts.iter().map(PartTrace::to_json).collect();
+        format!("{{\"parts\": [{}]}}", parts.join(", "))
+    }
+}
+
+impl PartTrace {
+    /// Records one step of reasoning, with the 1-based input line it concerns if any.
+    pub fn step(&mut self, line: Option<usize>, kind: &'static str, message: impl Into<String>) {
+        self.steps.push(Step {
+            line,
+            kind,
+            message: message.into(),
+        });
+    }
+
+    fn to_json(&self) -> String {
+        let steps: Vec<String> = self
+            .steps
+            .iter()
+            .map(|step| {
+                let line = step.line.map_or("null".into(), |line| line.to_string());
+                format!(
+                    "{{\"line\": {line}, \"kind\": {}, \"message\": {}}}",
+                    json::quote(step.kind),
+                    json::quote(&step.message)
+                )
+            })
+            .collect();
+
+        format!(
+            "{{\"part\": {}, \"answer\": {}, \"steps\": [{}]}}",
+            self.part,
+            json::quote(&self.answer),
+            steps.join(", ")
+        )
+    }
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for part in &self.parts {
+            writeln!(f, "Part {}: {}", part.part, part.answer)?;
+            for step in &part.steps {
+                match step.line {
+                    Some(line) => writeln!(f, "  line {line}: {}: {}", step.kind, step.message)?,
+                    None => writeln!(f, "  {}: {}", step.kind, step.message)?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_trace() -> Trace {
+        let mut trace = Trace::default();
+        trace.part(1, 7).step(Some(3), "mul", "mul(3,4) = 12");
+        trace.part(2, 5).step(None, "toggle", "don't()");
+        trace
+    }
+
+    #[test]
+    fn test_display() {
+        let expected = "Part 1: 7\n  line 3: mul: mul(3,4) = 12\nPart 2: 5\n  toggle: don't()\n";
+        assert_eq!(get_test_trace().to_string(), expected);
+    }
+
+    #[test]
+    fn test_to_json() {
+        let expected = "{\"parts\": [\
+            {\"part\": 1, \"answer\": \"7\", \"steps\": [{\"line\": 3, \"kind\": \"mul\", \"message\": \"mul(3,4) = 12\"}]}, \
+            {\"part\": 2, \"answer\": \"5\", \"steps\": [{\"line\": null, \"kind\": \"toggle\", \"message\": \"don't()\"}]}\
+            ]}";
+        assert_eq!(get_test_trace().to_json(), expected);
+    }
+}
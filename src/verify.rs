@@ -0,0 +1,240 @@
+use crate::{answer::Answer, json, solution::DayRun, toml};
+use std::{collections::HashMap, fmt, fs, io, path::Path, time::Duration};
+
+/// The file in each year's input directory that holds the accepted answers, as
+/// `dayN.partM = answer`. Fixture directories hold theirs the same way, keyed by fixture name.
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+#[derive(Debug, Default)]
+pub struct Answers {
+    answers: HashMap<(String, u32), Answer>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail,
+    /// There is no recorded answer to compare against.
+    Unchecked,
+}
+
+/// The result of checking one part of a day on one input against its recorded answer.
+#[derive(Debug)]
+pub struct PartCheck {
+    pub year: u32,
+    pub day: String,
+    /// The name of the input, which is the day's name for the puzzle input.
+    pub input: String,
+    pub part: u32,
+    pub expected: Option<Answer>,
+    pub actual: Result<Answer, String>,
+    pub elapsed: Duration,
+}
+
+impl Answers {
+    /// Reads the answers file. A missing file means no answers are recorded.
+    pub fn load(path: &Path) -> Result<Answers, String> {
+        match fs::read_to_string(path) {
+            Ok(answers_str) => {
+                Answers::from_toml(&answers_str).map_err(|e| format!("{}:{e}", path.display()))
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(format!("Could not read {}: {e}", path.display())),
+        }
+    }
+
+    fn from_toml(answers_str: &str) -> Result<Answers, String> {
+        let mut answers = HashMap::new();
+
+        for entry in toml::entries(answers_str)? {
+            let (input, part) = entry
+                .key
+                .rsplit_once(".part")
+                .and_then(|(input, part)| Some((input, part.parse().ok()?)))
+                .ok_or_else(|| {
+                    format!(
+                        "{}: expected `<input>.partM`, got {}",
+                        entry.line, entry.key
+                    )
+                })?;
+            // Only bare numbers are integers, so that a quoted "007" stays text.
+            let answer = match entry.quoted {
+                true => Answer::from(entry.value),
+                false => entry.value.parse().unwrap(),
+            };
+            answers.insert((input.to_string(), part), answer);
+        }
+
+        Ok(Answers { answers })
+    }
+
+    pub fn get(&self, input: &str, part: u32) -> Option<&Answer> {
+        self.answers.get(&(input.to_string(), part))
+    }
+}
+
+impl PartCheck {
+    /// The day across years, e.g. `2024/day6`.
+    pub fn day_id(&self) -> String {
+        format!("{}/{}", self.year, self.day)
+    }
+
+    /// Names the check, e.g. `2024/day6 part 1`, adding the input if it is not the puzzle
+    /// input.
+    pub fn name(&self) -> String {
+        match self.input == self.day {
+            true => format!("{} part {}", self.day_id(), self.part),
+            false => format!("{} part {} ({})", self.day_id(), self.part, self.input),
+        }
+    }
+
+    pub fn verdict(&self) -> Verdict {
+        match (&self.expected, &self.actual) {
+            (_, Err(_)) => Verdict::Fail,
+            (None, Ok(_)) => Verdict::Unchecked,
+            (Some(expected), Ok(actual)) if expected == actual => Verdict::Pass,
+            (Some(_), Ok(_)) => Verdict::Fail,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        let expected = self
+            .expected
+            .as_ref()
+            .map_or("null".into(), Answer::to_json);
+        let actual = match &self.actual {
+            Ok(answer) => format!("\"actual\": {}", answer.to_json()),
+            Err(e) => format!("\"error\": {}", json::quote(e)),
+        };
+
+        format!(
+            "{{\"year\": {}, \"day\": {}, \"input\": {}, \"part\": {}, \"verdict\": \"{}\", \"expected\": {expected}, {actual}, \"seconds\": {}}}",
+            self.year,
+            json::quote(&self.day),
+            json::quote(&self.input),
+            self.part,
+            self.verdict(),
+            self.elapsed.as_secs_f64()
+        )
+    }
+}
+
+/// Pairs each part of `day_run`, run on the input named `input`, with its recorded answer.
+pub fn check(
+    year: u32,
+    day: &str,
+    input: &str,
+    day_run: DayRun,
+    answers: &Answers,
+) -> Vec<PartCheck> {
+    day_run
+        .parts
+        .into_iter()
+        .map(|part_run| PartCheck {
+            year,
+            day: day.to_string(),
+            input: input.to_string(),
+            part: part_run.part,
+            expected: answers.get(input, part_run.part).cloned(),
+            actual: part_run.answer,
+            elapsed: part_run.elapsed,
+        })
+        .collect()
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail => write!(f, "fail"),
+            Verdict::Unchecked => write!(f, "unchecked"),
+        }
+    }
+}
+
+impl fmt::Display for PartCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.name())?;
+        match (&self.expected, &self.actual) {
+            (_, Err(e)) => write!(f, "FAILED with error: {e}"),
+            (None, Ok(actual)) => write!(f, "no recorded answer, got {actual}"),
+            (Some(expected), Ok(actual)) if expected == actual => write!(f, "ok"),
+            (Some(expected), Ok(actual)) => {
+                write!(f, "FAILED, expected {expected}, got {actual}")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::PartRun;
+
+    fn day_run(answers: Vec<Result<Answer, String>>) -> DayRun {
+        DayRun {
+            phases: vec![],
+            parts: answers
+                .into_iter()
+                .zip(1..)
+                .map(|(answer, part)| PartRun {
+                    part,
+                    answer,
+                    elapsed: Duration::from_millis(5),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_check() {
+        let answers = Answers::from_toml("day3.part1 = 161\nday3.part2 = 48").unwrap();
+        let checks = check(
+            2024,
+            "day3",
+            "day3",
+            day_run(vec![Ok(161.into()), Ok(50.into())]),
+            &answers,
+        );
+        let verdicts: Vec<Verdict> = checks.iter().map(PartCheck::verdict).collect();
+        assert_eq!(verdicts, vec![Verdict::Pass, Verdict::Fail]);
+        assert_eq!(checks[0].to_string(), "2024/day3 part 1: ok");
+        assert_eq!(
+            checks[1].to_string(),
+            "2024/day3 part 2: FAILED, expected 48, got 50"
+        );
+        assert_eq!(
+            checks[1].to_json(),
+            "{\"year\": 2024, \"day\": \"day3\", \"input\": \"day3\", \"part\": 2, \"verdict\": \"fail\", \"expected\": 48, \"actual\": 50, \"seconds\": 0.005}"
+        );
+
+        let checks = check(
+            2024,
+            "day4",
+            "example",
+            day_run(vec![Ok(1.into()), Err("boom".into())]),
+            &answers,
+        );
+        let verdicts: Vec<Verdict> = checks.iter().map(PartCheck::verdict).collect();
+        assert_eq!(verdicts, vec![Verdict::Unchecked, Verdict::Fail]);
+        assert_eq!(
+            checks[1].to_string(),
+            "2024/day4 part 2 (example): FAILED with error: boom"
+        );
+    }
+
+    #[test]
+    fn test_answers_quoted() {
+        let answers = Answers::from_toml("day3.part1 = 7\nday3.part2 = \"007\"").unwrap();
+        assert_eq!(answers.get("day3", 1), Some(&Answer::Int(7)));
+        assert_eq!(answers.get("day3", 2), Some(&Answer::Text("007".into())));
+    }
+
+    #[test]
+    fn test_answers_errors() {
+        assert_eq!(
+            Answers::from_toml("day3 = 161").unwrap_err(),
+            "1: expected `<input>.partM`, got day3"
+        );
+    }
+}
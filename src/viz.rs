@@ -0,0 +1,175 @@
+use crate::json;
+use std::{
+    fmt::Write as _,
+    io::{self, Write},
+    iter::Peekable,
+    thread,
+    time::Duration,
+};
+
+pub type Frames = Box<dyn Iterator<Item = String>>;
+
+pub fn play(frames: Frames, fps: u32) -> io::Result<()> {
+    let mut stdout = io::stdout().lock();
+    let delay = Duration::from_secs(1) / fps;
+    let mut height = 0;
+
+    for output in frame_outputs(frames) {
+        height = height.max(output.height);
+        stdout.write_all(output.text.as_bytes())?;
+        stdout.flush()?;
+        thread::sleep(delay);
+    }
+
+    write!(stdout, "\x1b[{};1H", height + 1)?;
+    stdout.flush()
+}
+
+/// Writes the frames as an asciicast v2 recording, one frame every `1 / fps` seconds.
+/// No wall-clock data is included, so the same frames and speed always give the same file.
+pub fn write_asciicast(
+    title: &str,
+    frames: Frames,
+    fps: u32,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    let mut frames = frames.peekable();
+    let (width, height) = frame_size(&mut frames);
+
+    writeln!(
+        out,
+        "{{\"version\": 2, \"width\": {width}, \"height\": {height}, \"title\": {}}}",
+        json::quote(title)
+    )?;
+
+    let mut num_frames = 0;
+    for (i, output) in frame_outputs(Box::new(frames)).enumerate() {
+        num_frames = i + 1;
+        if !output.text.is_empty() {
+            write_event(out, i, fps, &output.text)?;
+        }
+    }
+
+    write_event(out, num_frames, fps, &format!("\x1b[{};1H", height + 1))
+}
+
+fn write_event(out: &mut impl Write, frame: usize, fps: u32, text: &str) -> io::Result<()> {
+    let micros = frame as u64 * 1_000_000 / fps as u64;
+    writeln!(
+        out,
+        "[{}.{:06}, \"o\", {}]",
+        micros / 1_000_000,
+        micros % 1_000_000,
+        json::quote(text)
+    )
+}
+
+fn frame_size(frames: &mut Peekable<Frames>) -> (usize, usize) {
+    match frames.peek() {
+        Some(frame) => {
+            let width = frame.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+            (width, frame.lines().count())
+        }
+        None => (0, 0),
+    }
+}
+
+struct FrameOutput {
+    text: String,
+    height: usize,
+}
+
+fn frame_outputs(frames: Frames) -> impl Iterator<Item = FrameOutput> {
+    let mut prev: Option<String> = None;
+
+    frames.map(move |frame| {
+        let text = match &prev {
+            Some(prev) => frame_diff(prev, &frame),
+            None => format!(
+                "\x1b[2J\x1b[H{}",
+                frame.lines().collect::<Vec<_>>().join("\r\n")
+            ),
+        };
+        let height = frame.lines().count();
+        prev = Some(frame);
+        FrameOutput { text, height }
+    })
+}
+
+/// Terminal output that turns `prev` into `next` by redrawing only the cells that changed.
+fn frame_diff(prev: &str, next: &str) -> String {
+    let mut output = String::new();
+    let prev_lines: Vec<&str> = prev.lines().collect();
+    let next_lines: Vec<&str> = next.lines().collect();
+
+    for (row, next_line) in next_lines.iter().enumerate() {
+        let prev_line = prev_lines.get(row).copied().unwrap_or("");
+        line_diff(&mut output, row, prev_line, next_line);
+    }
+
+    for row in next_lines.len()..prev_lines.len() {
+        write!(output, "\x1b[{};1H\x1b[K", row + 1).unwrap();
+    }
+
+    output
+}
+
+fn line_diff(output: &mut String, row: usize, prev: &str, next: &str) {
+    let prev: Vec<char> = prev.chars().collect();
+    let next: Vec<char> = next.chars().collect();
+    let mut col = 0;
+
+    while col < next.len() {
+        if prev.get(col) == Some(&next[col]) {
+            col += 1;
+            continue;
+        }
+
+        let start = col;
+        while col < next.len() && prev.get(col) != Some(&next[col]) {
+            col += 1;
+        }
+        let changed: String = next[start..col].iter().collect();
+        write!(output, "\x1b[{};{}H{changed}", row + 1, start + 1).unwrap();
+    }
+
+    if prev.len() > next.len() {
+        write!(output, "\x1b[{};{}H\x1b[K", row + 1, next.len() + 1).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_frames() -> Frames {
+        Box::new(
+            vec![
+                "ab\ncd\n".to_string(),
+                "ab\nxd\n".to_string(),
+                "ab\nxd\n".to_string(),
+            ]
+            .into_iter(),
+        )
+    }
+
+    #[test]
+    fn test_frame_diff() {
+        assert_eq!(frame_diff("ab\ncd", "ab\ncd"), "");
+        assert_eq!(frame_diff("ab\ncd", "ab\nxy"), "\x1b[2;1Hxy");
+        assert_eq!(frame_diff("abcd", "xbcz"), "\x1b[1;1Hx\x1b[1;4Hz");
+        assert_eq!(frame_diff("abc\nd", "ab"), "\x1b[1;3H\x1b[K\x1b[2;1H\x1b[K");
+    }
+
+    #[test]
+    fn test_write_asciicast() {
+        let mut out = vec![];
+        write_asciicast("test", test_frames(), 4, &mut out).unwrap();
+
+        let expected = "{\"version\": 2, \"width\": 2, \"height\": 2, \"title\": \"test\"}\n\
+                        [0.000000, \"o\", \"\\u001b[2J\\u001b[Hab\\r\\ncd\"]\n\
+                        [0.250000, \"o\", \"\\u001b[2;1Hx\"]\n\
+                        [0.750000, \"o\", \"\\u001b[3;1H\"]\n";
+        assert_eq!(String::from_utf8(out).unwrap(), expected);
+    }
+}